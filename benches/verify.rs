@@ -0,0 +1,55 @@
+//! Compares verification with a [`PreparedPublicKey`] to the plain path, on the host.
+//!
+//! Run with `cargo bench --target x86_64-unknown-linux-gnu`; the Cortex-M4 counterpart
+//! is `qemu-tests/src/bin/bench.rs`.
+
+use std::{hint::black_box, time::Instant};
+
+use p256_cortex_m4::{PreparedPublicKey, PublicKey, SecretKey, Signature};
+use rand::thread_rng;
+
+const SIGNATURES: usize = 256;
+
+/// Average time per call of `f` over `SIGNATURES` calls, in microseconds.
+fn time(name: &str, mut f: impl FnMut(usize)) {
+    let start = Instant::now();
+    for i in 0..SIGNATURES {
+        f(i);
+    }
+    let average = start.elapsed().as_secs_f64() * 1e6 / SIGNATURES as f64;
+    println!("{:<40} {:>10.1} µs", name, average);
+}
+
+fn main() {
+    let secret_key = SecretKey::random(thread_rng());
+    let public_key = secret_key.public_key();
+    let hashes: Vec<[u8; 32]> = (0..SIGNATURES).map(|i| [i as u8; 32]).collect();
+    let signatures: Vec<Signature> = hashes
+        .iter()
+        .map(|hash| secret_key.sign_prehashed(hash, thread_rng()))
+        .collect();
+    let items: Vec<(&PublicKey, &[u8], &Signature)> = hashes
+        .iter()
+        .zip(signatures.iter())
+        .map(|(hash, signature)| (&public_key, hash.as_ref(), signature))
+        .collect();
+
+    let prepared = PreparedPublicKey::new(public_key.clone());
+
+    time("PublicKey::verify_prehashed", |i| {
+        assert!(black_box(&public_key).verify_prehashed(&hashes[i], &signatures[i]));
+    });
+    time("PreparedPublicKey::new", |_| {
+        black_box(PreparedPublicKey::new(black_box(&public_key).clone()));
+    });
+    time("PreparedPublicKey::verify_prehashed", |i| {
+        assert!(black_box(&prepared).verify_prehashed(&hashes[i], &signatures[i]));
+    });
+    let start = Instant::now();
+    assert!(PublicKey::verify_prehashed_batch(black_box(&items)).all(|authentic| authentic));
+    let average = start.elapsed().as_secs_f64() * 1e6 / SIGNATURES as f64;
+    println!(
+        "{:<40} {:>10.1} µs",
+        "PublicKey::verify_prehashed_batch", average
+    );
+}
@@ -0,0 +1,63 @@
+//! Compares verification with a `PreparedPublicKey` to the plain path, on the Cortex-M4
+//! backend.
+//!
+//! Counts SysTick ticks at the core clock, which on hardware are cycles. Under QEMU
+//! (`make qemu-bench`), `-icount` makes the counts proportional to executed instructions,
+//! so only their ratios are meaningful.
+
+#![no_std]
+#![no_main]
+
+use cortex_m::peripheral::{syst::SystClkSource, SYST};
+use cortex_m_rt::entry;
+use cortex_m_semihosting::{debug, hprintln};
+use p256_cortex_m4::{PreparedPublicKey, SecretKey, Signature};
+use panic_semihosting as _;
+
+const SIGNATURES: usize = 16;
+
+/// Average SysTick ticks per call of `f` over `SIGNATURES` calls.
+///
+/// Each call must take less than 2^24 ticks, the SysTick counter wraps around once.
+fn ticks(name: &str, mut f: impl FnMut(usize)) {
+    let mut total = 0;
+    for i in 0..SIGNATURES {
+        let start = SYST::get_current();
+        f(i);
+        let end = SYST::get_current();
+        total += start.wrapping_sub(end) & 0x00ff_ffff;
+    }
+    hprintln!("{:<40} {:>10}", name, total / SIGNATURES as u32);
+}
+
+#[entry]
+fn main() -> ! {
+    let mut syst = cortex_m::Peripherals::take().unwrap().SYST;
+    syst.set_clock_source(SystClkSource::Core);
+    syst.set_reload(0x00ff_ffff);
+    syst.clear_current();
+    syst.enable_counter();
+
+    let secret_key = SecretKey::from_bytes([0x42; 32]).unwrap();
+    let public_key = secret_key.public_key();
+    let hashes: [[u8; 32]; SIGNATURES] = core::array::from_fn(|i| [i as u8; 32]);
+    let signatures: [Signature; SIGNATURES] =
+        core::array::from_fn(|i| secret_key.sign_prehashed_deterministic(&hashes[i]));
+
+    let prepared = PreparedPublicKey::new(public_key.clone());
+
+    ticks("PublicKey::verify_prehashed", |i| {
+        assert!(public_key.verify_prehashed(&hashes[i], &signatures[i]));
+    });
+    ticks("PreparedPublicKey::new", |_| {
+        core::hint::black_box(PreparedPublicKey::new(public_key.clone()));
+    });
+    ticks("PreparedPublicKey::verify_prehashed", |i| {
+        assert!(prepared.verify_prehashed(&hashes[i], &signatures[i]));
+    });
+
+    debug::exit(debug::EXIT_SUCCESS);
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
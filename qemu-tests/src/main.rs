@@ -0,0 +1,66 @@
+//! Runs the backend conformance suite, and the Wycheproof and CAVP vectors, against the
+//! Cortex-M4 backend.
+//!
+//! Intended for `qemu-system-arm -machine mps2-an386`, see `.cargo/config.toml`.
+//! Results are reported via semihosting, a failing test panics, which exits
+//! QEMU with a non-zero status.
+
+#![no_std]
+#![no_main]
+
+use core::mem::MaybeUninit;
+
+use cortex_m_rt::entry;
+use cortex_m_semihosting::{debug, hprint, hprintln};
+use embedded_alloc::LlffHeap as Heap;
+use panic_semihosting as _;
+
+#[path = "../../tests/cavp/suite.rs"]
+mod cavp;
+#[path = "../../tests/conformance/suite.rs"]
+mod conformance;
+#[path = "../../tests/wycheproof/suite.rs"]
+mod wycheproof;
+
+/// The Wycheproof vectors are parsed into owned test groups.
+const HEAP_SIZE: usize = 2 * 1024 * 1024;
+
+/// Tests of a suite, by name.
+type Tests = &'static [(&'static str, fn())];
+
+#[global_allocator]
+static HEAP: Heap = Heap::empty();
+
+#[entry]
+fn main() -> ! {
+    {
+        static mut HEAP_MEM: [MaybeUninit<u8>; HEAP_SIZE] = [MaybeUninit::uninit(); HEAP_SIZE];
+        #[allow(static_mut_refs)]
+        unsafe {
+            HEAP.init(HEAP_MEM.as_ptr() as usize, HEAP_SIZE)
+        }
+    }
+
+    let suites: [(&str, Tests); 3] = [
+        ("conformance", conformance::TESTS),
+        ("wycheproof", wycheproof::TESTS),
+        ("cavp", cavp::TESTS),
+    ];
+
+    let mut passed = 0;
+    for (suite, tests) in suites {
+        hprintln!("running {} {} tests", tests.len(), suite);
+        for (name, test) in tests {
+            hprint!("test {}::{} ... ", suite, name);
+            test();
+            hprintln!("ok");
+            passed += 1;
+        }
+    }
+    hprintln!("test result: ok. {} passed", passed);
+
+    debug::exit(debug::EXIT_SUCCESS);
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
@@ -0,0 +1,111 @@
+//! ASN.1 DER encodings, shared by both backends.
+
+use crate::{Error, Result};
+
+/// `id-ecPublicKey` in [RFC 5480][rfc-5480], section 2.1.1.
+///
+/// [rfc-5480]: https://www.rfc-editor.org/rfc/rfc5480
+#[cfg(feature = "spki")]
+pub(crate) const ID_EC_PUBLIC_KEY: der::asn1::ObjectIdentifier =
+    der::asn1::ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+/// `secp256r1` in [RFC 5480][rfc-5480], section 2.1.1.1, a.k.a. `prime256v1`.
+///
+/// [rfc-5480]: https://www.rfc-editor.org/rfc/rfc5480
+#[cfg(feature = "spki")]
+pub(crate) const SECP256R1: der::asn1::ObjectIdentifier =
+    der::asn1::ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+
+/// `AlgorithmIdentifier` in [RFC 5280][rfc-5280], section 4.1.1.2.
+///
+/// [rfc-5280]: https://www.rfc-editor.org/rfc/rfc5280
+#[cfg(feature = "spki")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, der::Sequence)]
+pub(crate) struct AlgorithmIdentifier<'a> {
+    pub algorithm: der::asn1::ObjectIdentifier,
+    pub parameters: Option<der::asn1::AnyRef<'a>>,
+}
+
+#[cfg(feature = "spki")]
+impl AlgorithmIdentifier<'_> {
+    /// `id-ecPublicKey` with the named curve `secp256r1`.
+    pub fn p256() -> AlgorithmIdentifier<'static> {
+        AlgorithmIdentifier {
+            algorithm: ID_EC_PUBLIC_KEY,
+            parameters: Some((&SECP256R1).into()),
+        }
+    }
+
+    /// Only [`Self::p256`] is supported, explicit curve parameters are not.
+    pub fn check(&self) -> Result<()> {
+        match self.parameters.map(|parameters| parameters.decode_as()) {
+            Some(Ok(SECP256R1)) if self.algorithm == ID_EC_PUBLIC_KEY => Ok(()),
+            _ => Err(Error::UnsupportedAlgorithm),
+        }
+    }
+}
+
+/// Decodes PEM with the expected label.
+///
+/// The output is zeroizing, as it may contain a secret key.
+#[cfg(feature = "pem")]
+pub(crate) fn decode_pem(
+    pem: &str,
+    label: &str,
+) -> Result<zeroize::Zeroizing<alloc::vec::Vec<u8>>> {
+    let (actual, der) = der::pem::decode_vec(pem.as_bytes()).map_err(|_| Error::InvalidEncoding)?;
+    let der = zeroize::Zeroizing::new(der);
+    if actual != label {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok(der)
+}
+
+/// Encodes PEM with the given label, and LF line endings.
+#[cfg(feature = "pem")]
+pub(crate) fn encode_pem(der: &[u8], label: &str) -> alloc::string::String {
+    der::pem::encode_string(label, der::pem::LineEnding::LF, der).unwrap()
+}
+
+/// `ECDSA-Sig-Value` in [SEC 1][sec-1], section C.5.
+///
+/// [sec-1]: http://www.secg.org/sec1-v2.pdf
+// NB: the fallback decodes signatures without `sec1-signatures`, as it always has
+#[cfg(any(feature = "sec1-signatures", not(cortex_m4)))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, der::Sequence)]
+struct DerSignature<'a> {
+    pub r: der::asn1::UintRef<'a>,
+    pub s: der::asn1::UintRef<'a>,
+}
+
+/// Decodes to big-endian r, then big-endian s.
+///
+/// Decoding is strict: integers must be minimally encoded, and there must be no trailing data.
+/// The range of r and s is only checked to fit into 256 bits.
+#[cfg(any(feature = "sec1-signatures", not(cortex_m4)))]
+pub(crate) fn decode_signature(bytes: &[u8]) -> Result<[u8; 64]> {
+    use der::Decode;
+    let signature = DerSignature::from_der(bytes)?;
+    let r = signature.r.as_bytes();
+    let s = signature.s.as_bytes();
+    if r.len() > 32 || s.len() > 32 {
+        return Err(Error::ScalarOutOfRange);
+    }
+
+    let mut untagged = [0u8; 64];
+    untagged[32 - r.len()..32].copy_from_slice(r);
+    untagged[64 - s.len()..].copy_from_slice(s);
+    Ok(untagged)
+}
+
+/// Encodes big-endian r, then big-endian s, returning length.
+#[cfg(feature = "sec1-signatures")]
+pub(crate) fn encode_signature(untagged: &[u8; 64], buffer: &mut [u8; 72]) -> usize {
+    let signature = DerSignature {
+        r: der::asn1::UintRef::new(&untagged[..32]).unwrap(),
+        s: der::asn1::UintRef::new(&untagged[32..]).unwrap(),
+    };
+
+    use der::Encode;
+    signature.encode_to_slice(buffer.as_mut()).unwrap().len()
+}
@@ -0,0 +1,250 @@
+//! Minimal deterministically encoded CBOR, as needed for COSE.
+//!
+//! Only definite lengths and the shortest form of arguments are produced and accepted,
+//! see [RFC 8949][rfc-8949], section 4.2.1. Malformed input is [`Error::InvalidEncoding`],
+//! a too small buffer is [`Error::InvalidLength`].
+//!
+//! [rfc-8949]: https://www.rfc-editor.org/rfc/rfc8949
+
+use crate::{Error, Result};
+
+pub(crate) const UNSIGNED: u8 = 0;
+pub(crate) const NEGATIVE: u8 = 1;
+pub(crate) const BYTES: u8 = 2;
+pub(crate) const TEXT: u8 = 3;
+pub(crate) const ARRAY: u8 = 4;
+pub(crate) const MAP: u8 = 5;
+pub(crate) const TAG: u8 = 6;
+pub(crate) const SIMPLE: u8 = 7;
+
+const FALSE: u64 = 20;
+const TRUE: u64 = 21;
+const NULL: u64 = 22;
+
+/// Nesting depth up to which [`Decoder::skip`] descends.
+const MAX_DEPTH: usize = 16;
+
+/// Head of a data item, with the argument in the shortest form.
+pub(crate) fn head(major: u8, argument: u64, buffer: &mut [u8; 9]) -> &[u8] {
+    let major = major << 5;
+    match argument {
+        0..=23 => {
+            buffer[0] = major | argument as u8;
+            &buffer[..1]
+        }
+        24..=0xff => {
+            buffer[..2].copy_from_slice(&[major | 24, argument as u8]);
+            &buffer[..2]
+        }
+        0x100..=0xffff => {
+            buffer[0] = major | 25;
+            buffer[1..3].copy_from_slice(&(argument as u16).to_be_bytes());
+            &buffer[..3]
+        }
+        0x1_0000..=0xffff_ffff => {
+            buffer[0] = major | 26;
+            buffer[1..5].copy_from_slice(&(argument as u32).to_be_bytes());
+            &buffer[..5]
+        }
+        _ => {
+            buffer[0] = major | 27;
+            buffer[1..].copy_from_slice(&argument.to_be_bytes());
+            buffer
+        }
+    }
+}
+
+pub(crate) struct Encoder<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    /// Length of the encoding so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn raw(&mut self, bytes: &[u8]) -> Result<()> {
+        let end = self.len + bytes.len();
+        self.buffer
+            .get_mut(self.len..end)
+            .ok_or(Error::InvalidLength)?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    pub fn head(&mut self, major: u8, argument: u64) -> Result<()> {
+        self.raw(head(major, argument, &mut [0u8; 9]))
+    }
+
+    pub fn int(&mut self, value: i64) -> Result<()> {
+        if value >= 0 {
+            self.head(UNSIGNED, value as u64)
+        } else {
+            // -1 - value
+            self.head(NEGATIVE, !value as u64)
+        }
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.head(BYTES, bytes.len() as u64)?;
+        self.raw(bytes)
+    }
+
+    pub fn null(&mut self) -> Result<()> {
+        self.head(SIMPLE, NULL)
+    }
+}
+
+pub(crate) struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// There must be no trailing data.
+    pub fn finish(self) -> Result<()> {
+        match self.bytes.is_empty() {
+            true => Ok(()),
+            false => Err(Error::InvalidEncoding),
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.bytes.len() {
+            return Err(Error::InvalidEncoding);
+        }
+        let (taken, bytes) = self.bytes.split_at(len);
+        self.bytes = bytes;
+        Ok(taken)
+    }
+
+    /// Major type of the next data item.
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.first().map(|initial| initial >> 5)
+    }
+
+    pub fn head(&mut self) -> Result<(u8, u64)> {
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let (argument, minimum) = match info {
+            0..=23 => return Ok((major, info as u64)),
+            24 => (self.take(1)?[0] as u64, 24),
+            25 => (
+                u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+                0x100,
+            ),
+            26 => (
+                u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+                0x1_0000,
+            ),
+            27 => (
+                u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+                0x1_0000_0000,
+            ),
+            // reserved, or indefinite length
+            _ => return Err(Error::InvalidEncoding),
+        };
+        // floats are not supported, and simple values 0..=31 are not well-formed in two bytes
+        if argument < minimum || (major == SIMPLE && (info != 24 || argument < 32)) {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok((major, argument))
+    }
+
+    pub fn expect(&mut self, major: u8) -> Result<u64> {
+        match self.head()? {
+            (actual, argument) if actual == major => Ok(argument),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    pub fn int(&mut self) -> Result<i64> {
+        match self.head()? {
+            (UNSIGNED, argument) => i64::try_from(argument).map_err(|_| Error::InvalidEncoding),
+            (NEGATIVE, argument) => i64::try_from(argument)
+                .map(|argument| !argument)
+                .map_err(|_| Error::InvalidEncoding),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.expect(BYTES)?;
+        self.take(usize::try_from(len).map_err(|_| Error::InvalidEncoding)?)
+    }
+
+    /// Either `null`, or a byte string.
+    pub fn null_or_bytes(&mut self) -> Result<Option<&'a [u8]>> {
+        if self.bytes.first() == Some(&(SIMPLE << 5 | NULL as u8)) {
+            self.take(1)?;
+            return Ok(None);
+        }
+        self.bytes().map(Some)
+    }
+
+    /// Skips a data item of any type, which must be well-formed.
+    pub fn skip(&mut self) -> Result<()> {
+        self.skip_nested(MAX_DEPTH)
+    }
+
+    fn skip_nested(&mut self, depth: usize) -> Result<()> {
+        let depth = depth.checked_sub(1).ok_or(Error::InvalidEncoding)?;
+        let (major, argument) = self.head()?;
+        let len = usize::try_from(argument).map_err(|_| Error::InvalidEncoding);
+        match major {
+            BYTES => {
+                self.take(len?)?;
+            }
+            TEXT => {
+                core::str::from_utf8(self.take(len?)?).map_err(|_| Error::InvalidEncoding)?;
+            }
+            ARRAY => {
+                for _ in 0..argument {
+                    self.skip_nested(depth)?;
+                }
+            }
+            MAP => {
+                for _ in 0..argument {
+                    self.skip_nested(depth)?;
+                    self.skip_nested(depth)?;
+                }
+            }
+            TAG => self.skip_nested(depth)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn bool(&mut self) -> Result<bool> {
+        match self.expect(SIMPLE)? {
+            FALSE => Ok(false),
+            TRUE => Ok(true),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    /// Label of a map entry, which must sort after the previous label.
+    ///
+    /// Map keys are sorted bytewise lexicographically by their encoding, which also
+    /// rules out duplicates. Start with an empty `previous` label.
+    pub fn label(&mut self, previous: &mut &'a [u8]) -> Result<i64> {
+        let start = self.bytes;
+        let label = self.int()?;
+        let encoded = &start[..start.len() - self.bytes.len()];
+        if encoded <= *previous {
+            return Err(Error::InvalidEncoding);
+        }
+        *previous = encoded;
+        Ok(label)
+    }
+}
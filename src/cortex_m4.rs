@@ -1,25 +1,49 @@
 use core::{convert::TryInto, mem::MaybeUninit};
 
 use rand_core::{CryptoRng, RngCore};
-use zeroize::{Zeroize, Zeroizing};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+#[cfg(feature = "rfc6979")]
+use crate::nonce::Nonces;
 #[cfg(feature = "prehash")]
 use crate::sha256;
-use crate::{Error, Result};
+use crate::{bits2field, Error, RecoveryId, Result};
+
+#[cfg(feature = "hash2curve")]
+mod hash2curve;
+mod point;
+mod scalar;
+mod verify;
+
+pub use point::{AffinePoint, ProjectivePoint};
+pub use scalar::Scalar;
 
 /// NIST P-256 secret key.
 ///
 /// The internal representation is as little-endian (native) words.
-#[derive(Clone, Zeroize)]
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub struct SecretKey([u32; 8]);
 
 /// NIST P-256 public key.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PublicKey {
     x: [u32; 8],
     y: [u32; 8],
 }
 
+/// Number of distinct public keys whose preparation [`PublicKey::verify_prehashed_batch`] keeps.
+const BATCH_KEYS: usize = 4;
+
+/// NIST P-256 public key, prepared for verifying many signatures.
+///
+/// The conversion to Montgomery form and the table of odd multiples of the key
+/// are computed once, instead of once per signature in [`PublicKey::verify_prehashed`].
+#[derive(Clone)]
+pub struct PreparedPublicKey {
+    public: PublicKey,
+    table: verify::Table,
+}
+
 /// NIST P-256 keypair.
 #[derive(Clone)]
 pub struct Keypair {
@@ -39,11 +63,17 @@ pub struct Signature {
     s: [u32; 8],
 }
 
+/// Precomputed `(r, k^-1)` for a single ECDSA signature.
+///
+/// Obtained from [`SecretKey::presign`], consumed by [`SecretKey::sign_prehashed_with`].
+/// Zeroized on drop.
+pub struct Presignature(p256_cortex_m4_sys::SignPrecomp);
+
 /// Outcome of ECDH key agreement.
 ///
 /// The x-coordinate of the multiplication of a secret key and a public key,
 /// represented as big-endian integer.
-#[derive(Clone, Zeroize)]
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub struct SharedSecret([u8; 32]);
 
 impl Keypair {
@@ -51,29 +81,48 @@ impl Keypair {
     ///
     /// The implementation uses rejection sampling.
     pub fn random(rng: impl CryptoRng + RngCore) -> Self {
-        let mut keypair = Keypair {
-            public: PublicKey {
-                x: [0u32; 8],
-                y: [0u32; 8],
-            },
-            secret: SecretKey([0u32; 8]),
-        };
+        let secret = SecretKey::random(rng);
+        let public = secret.public_key();
+        Keypair { public, secret }
+    }
+}
 
-        let mut rng = rng;
-        loop {
-            rng.fill_bytes(unsafe {
-                core::mem::transmute::<&mut [u32; 8], &mut [u8; 32]>(&mut keypair.secret.0)
-            });
-            let valid = unsafe {
-                p256_cortex_m4_sys::p256_keygen(
-                    &mut keypair.public.x[0] as *mut _,
-                    &mut keypair.public.y[0] as _,
-                    &keypair.secret.0[0] as _,
-                )
-            };
-            if valid {
-                return keypair;
-            }
+/// Order of the base point, as little-endian words.
+const ORDER: [u32; 8] = [
+    0xfc632551, 0xf3b9cac2, 0xa7179e84, 0xbce6faad, 0xffffffff, 0xffffffff, 0x00000000, 0xffffffff,
+];
+
+/// One, in Montgomery form.
+const ONE_MONTGOMERY: [u32; 8] = [1, 0, 0, 0xffffffff, 0xffffffff, 0xffffffff, 0xfffffffe, 0];
+
+/// Converts a field element to Montgomery form, in place.
+fn to_montgomery(element: &mut [u32; 8]) {
+    let element = element.as_mut_ptr();
+    unsafe { p256_cortex_m4_sys::P256_to_montgomery(element, element) };
+}
+
+/// Converts a field element from Montgomery form, in place.
+fn from_montgomery(element: &mut [u32; 8]) {
+    let element = element.as_mut_ptr();
+    unsafe { p256_cortex_m4_sys::P256_from_montgomery(element, element) };
+}
+
+/// Draws big-endian 256-bit integers from `rng`, until one is in the range 1..=n-1.
+///
+/// This consumes `rng` exactly like the rejection sampling in `p256`.
+fn random_scalar(rng: &mut impl RngCore, scalar: &mut [u32; 8]) {
+    let mut bytes = Zeroizing::new([0u8; 32]);
+    loop {
+        rng.fill_bytes(bytes.as_mut());
+        unsafe {
+            p256_cortex_m4_sys::p256_convert_endianness(
+                &mut scalar[0] as *mut u32 as *mut _,
+                &bytes[0] as *const u8 as *const _,
+                32,
+            )
+        };
+        if unsafe { p256_cortex_m4_sys::P256_check_range_n(&scalar[0] as *const u32) } {
+            return;
         }
     }
 }
@@ -85,23 +134,15 @@ impl SecretKey {
     pub fn random(rng: impl CryptoRng + RngCore) -> Self {
         let mut secret = SecretKey([0u32; 8]);
         let mut rng = rng;
-        loop {
-            rng.fill_bytes(unsafe {
-                core::mem::transmute::<&mut [u32; 8], &mut [u8; 32]>(&mut secret.0)
-            });
-            let valid =
-                unsafe { p256_cortex_m4_sys::P256_check_range_n(&secret.0[0] as *const u32) };
-            if valid {
-                return secret;
-            }
-        }
+        random_scalar(&mut rng, &mut secret.0);
+        secret
     }
 
     /// Verifies that there are 32 bytes that correspond to a big-endian integer in the range 1..=n-1.
     pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
         let bytes = bytes.as_ref();
         if bytes.len() != 32 {
-            return Err(Error);
+            return Err(Error::InvalidLength);
         }
 
         let mut secret = SecretKey([0u32; 8]);
@@ -114,7 +155,7 @@ impl SecretKey {
         };
 
         if !unsafe { p256_cortex_m4_sys::P256_check_range_n(&secret.0[0] as *const u32) } {
-            return Err(Error);
+            return Err(Error::ScalarOutOfRange);
         }
         Ok(secret)
     }
@@ -135,6 +176,8 @@ impl SecretKey {
     #[allow(unused_unsafe)]
     /// Convert endianness to obtain the big-endian representation of the secret scalar as 32 bytes.
     ///
+    /// # Safety
+    ///
     /// "unsafe" because the caller is responsible for keeping the value secret.
     pub unsafe fn to_bytes(&self) -> [u8; 32] {
         let mut big_endian = [0u8; 32];
@@ -180,12 +223,173 @@ impl SecretKey {
         let mut k = Zeroizing::<[u32; 8]>::new([0u32; 8]);
         let mut rng = rng;
         loop {
-            rng.fill_bytes(unsafe { core::mem::transmute::<&mut [u32; 8], &mut [u8; 32]>(&mut k) });
+            random_scalar(&mut rng, &mut k);
             if unsafe {
                 p256_cortex_m4_sys::p256_sign(
                     &mut signature.r[0] as *mut u32,
                     &mut signature.s[0] as *mut u32,
-                    &prehashed_message[0] as *const u8,
+                    prehashed_message.as_ptr(),
+                    prehashed_message.len() as u32,
+                    &self.0 as *const u32,
+                    &k[0] as *const u32,
+                )
+            } {
+                return signature;
+            }
+        }
+    }
+
+    #[cfg(feature = "rfc6979")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rfc6979")))]
+    /// Deterministic signature on message assumed to be hashed, if needed.
+    ///
+    /// Internally, draws `k` from the HMAC-DRBG of [RFC 6979][rfc-6979], until signing succeeds.
+    ///
+    /// [rfc-6979]: https://datatracker.ietf.org/doc/html/rfc6979
+    pub fn sign_prehashed_deterministic(&self, prehashed_message: &[u8]) -> Signature {
+        let secret = Zeroizing::new(unsafe { self.to_bytes() });
+        self.sign_prehashed_with_nonces(
+            prehashed_message,
+            Nonces::new(&secret, prehashed_message, &[]),
+        )
+    }
+
+    #[cfg(feature = "rfc6979")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rfc6979")))]
+    /// Hedged signature on message assumed to be hashed, if needed.
+    ///
+    /// Internally, draws `k` from the HMAC-DRBG of [RFC 6979][rfc-6979], seeded with additional
+    /// randomness from `rng` as in section 3.6, until signing succeeds. This protects against
+    /// both a weak RNG and fault attacks on purely deterministic signatures, see also
+    /// [draft-irtf-cfrg-det-sigs-with-noise][noise].
+    ///
+    /// [rfc-6979]: https://datatracker.ietf.org/doc/html/rfc6979
+    /// [noise]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-det-sigs-with-noise/
+    pub fn sign_prehashed_hedged(
+        &self,
+        prehashed_message: &[u8],
+        rng: impl CryptoRng + RngCore,
+    ) -> Signature {
+        let mut rng = rng;
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(entropy.as_mut());
+        let secret = Zeroizing::new(unsafe { self.to_bytes() });
+        self.sign_prehashed_with_nonces(
+            prehashed_message,
+            Nonces::new(&secret, prehashed_message, entropy.as_ref()),
+        )
+    }
+
+    /// First step of a two-step signature, which does not depend on the message.
+    ///
+    /// This accounts for nearly all of the time spent signing.
+    /// Internally, draws 256-bit `k` repeatedly, until the precomputation succeeds.
+    pub fn presign(&self, rng: impl CryptoRng + RngCore) -> Presignature {
+        let mut presignature = Presignature(p256_cortex_m4_sys::SignPrecomp {
+            r: [0u32; 8],
+            k_inv: [0u32; 8],
+        });
+        let mut k = Zeroizing::<[u32; 8]>::new([0u32; 8]);
+        let mut rng = rng;
+        loop {
+            random_scalar(&mut rng, &mut k);
+            if unsafe {
+                p256_cortex_m4_sys::p256_sign_step1(&mut presignature.0, &k[0] as *const u32)
+            } {
+                return presignature;
+            }
+        }
+    }
+
+    /// Second step of a two-step signature on message assumed to be hashed, if needed.
+    ///
+    /// With negligible probability, this fails, and signing must start over with a new
+    /// presignature.
+    pub fn sign_prehashed_with(
+        &self,
+        presignature: Presignature,
+        prehashed_message: &[u8],
+    ) -> Result<Signature> {
+        let mut presignature = presignature;
+        let mut signature = Signature {
+            r: [0u32; 8],
+            s: [0u32; 8],
+        };
+        if unsafe {
+            p256_cortex_m4_sys::p256_sign_step2(
+                &mut signature.r[0] as *mut u32,
+                &mut signature.s[0] as *mut u32,
+                prehashed_message.as_ptr(),
+                prehashed_message.len() as u32,
+                &self.0 as *const u32,
+                &mut presignature.0,
+            )
+        } {
+            Ok(signature)
+        } else {
+            Err(Error::SigningFailed)
+        }
+    }
+
+    /// Non-deterministic signature on message assumed to be hashed, if needed, together
+    /// with the [`RecoveryId`] to recover the public key from it.
+    ///
+    /// Internally, draws 256-bit `k` repeatedly, until signing succeeds.
+    pub fn sign_prehashed_recoverable(
+        &self,
+        prehashed_message: &[u8],
+        rng: impl CryptoRng + RngCore,
+    ) -> (Signature, RecoveryId) {
+        let mut k = Zeroizing::<[u32; 8]>::new([0u32; 8]);
+        let mut x = [0u32; 8];
+        let mut y = [0u32; 8];
+        let mut rng = rng;
+        loop {
+            random_scalar(&mut rng, &mut k);
+            // NB: this is `p256_sign_step1`, keeping the y-coordinate of `kG`
+            let mut presignature = Presignature(p256_cortex_m4_sys::SignPrecomp {
+                r: [0u32; 8],
+                k_inv: [0u32; 8],
+            });
+            unsafe {
+                p256_cortex_m4_sys::p256_scalarmult_base(&mut x[0], &mut y[0], &k[0]);
+                p256_cortex_m4_sys::P256_mod_n_inv(&mut presignature.0.k_inv[0], &k[0]);
+                p256_cortex_m4_sys::P256_reduce_mod_n_32bytes(&mut presignature.0.r[0], &x[0]);
+            }
+            let recovery_id = RecoveryId::new(y[0] & 1 == 1, presignature.0.r != x);
+            // NB: `p256_sign_step2` also rejects `r = 0`
+            if let Ok(signature) = self.sign_prehashed_with(presignature, prehashed_message) {
+                return (signature, recovery_id);
+            }
+        }
+    }
+
+    #[cfg(feature = "rfc6979")]
+    fn sign_prehashed_with_nonces(
+        &self,
+        prehashed_message: &[u8],
+        mut nonces: Nonces,
+    ) -> Signature {
+        let mut signature = Signature {
+            r: [0u32; 8],
+            s: [0u32; 8],
+        };
+        let mut k = Zeroizing::<[u32; 8]>::new([0u32; 8]);
+        loop {
+            let k_bytes = nonces.next();
+            unsafe {
+                p256_cortex_m4_sys::p256_convert_endianness(
+                    &mut k[0] as *mut u32 as *mut _,
+                    &k_bytes[0] as *const u8 as *const _,
+                    32,
+                )
+            };
+            // NB: `p256_sign` also rejects `k` outside of 1..=n-1
+            if unsafe {
+                p256_cortex_m4_sys::p256_sign(
+                    &mut signature.r[0] as *mut u32,
+                    &mut signature.s[0] as *mut u32,
+                    prehashed_message.as_ptr(),
                     prehashed_message.len() as u32,
                     &self.0 as *const u32,
                     &k[0] as *const u32,
@@ -204,6 +408,22 @@ impl SecretKey {
         self.sign_prehashed(prehashed_message.as_ref(), rng)
     }
 
+    #[cfg(all(feature = "prehash", feature = "rfc6979"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "prehash", feature = "rfc6979"))))]
+    /// Deterministic signature on message, which is hashed with SHA-256 first.
+    pub fn sign_deterministic(&self, message: &[u8]) -> Signature {
+        let prehashed_message = sha256(message);
+        self.sign_prehashed_deterministic(prehashed_message.as_ref())
+    }
+
+    #[cfg(all(feature = "prehash", feature = "rfc6979"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "prehash", feature = "rfc6979"))))]
+    /// Hedged signature on message, which is hashed with SHA-256 first.
+    pub fn sign_hedged(&self, message: &[u8], rng: impl CryptoRng + RngCore) -> Signature {
+        let prehashed_message = sha256(message);
+        self.sign_prehashed_hedged(prehashed_message.as_ref(), rng)
+    }
+
     /// ECDH key agreement.
     pub fn agree(&self, other: &PublicKey) -> SharedSecret {
         let mut shared = SharedSecret([0u8; 32]);
@@ -227,7 +447,7 @@ impl PublicKey {
     /// In other words, the uncompressed SEC1 format, without the leading 0x04 byte tag.
     pub fn from_untagged_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.len() != 64 {
-            return Err(Error);
+            return Err(Error::InvalidLength);
         }
         let mut sec1_bytes = [4u8; 65];
         sec1_bytes[1..].copy_from_slice(bytes);
@@ -241,6 +461,12 @@ impl PublicKey {
     ///
     /// [sec-1]: http://www.secg.org/sec1-v2.pdf
     pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
+        // NB: `p256_octet_string_to_point` would also accept the hybrid encoding
+        match (bytes.first(), bytes.len()) {
+            (Some(2 | 3), 33) | (Some(4), 65) => {}
+            (Some(2..=4) | None, _) => return Err(Error::InvalidLength),
+            _ => return Err(Error::InvalidEncoding),
+        }
         // NB: https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#initializing-a-struct-field-by-field
         let mut public = PublicKey {
             x: [0u32; 8],
@@ -250,13 +476,13 @@ impl PublicKey {
             p256_cortex_m4_sys::p256_octet_string_to_point(
                 &mut public.x[0] as *mut _,
                 &mut public.y[0] as *mut _,
-                &bytes[0] as *const _,
+                bytes.as_ptr(),
                 bytes.len() as u32,
             )
         } {
-            return Ok(public);
+            Ok(public)
         } else {
-            return Err(Error);
+            Err(Error::PointNotOnCurve)
         }
     }
 
@@ -308,7 +534,7 @@ impl PublicKey {
             p256_cortex_m4_sys::p256_verify(
                 &self.x[0] as *const u32,
                 &self.y[0] as *const u32,
-                &prehashed_message[0] as *const u8,
+                prehashed_message.as_ptr(),
                 prehashed_message.len() as u32,
                 &signature.r[0] as *const u32,
                 &signature.s[0] as *const u32,
@@ -324,11 +550,178 @@ impl PublicKey {
         let prehashed_message = sha256(message);
         self.verify_prehashed(prehashed_message.as_ref(), signature)
     }
+
+    /// Verify signatures on messages assumed to be hashed, if needed, yielding whether each
+    /// is authentic, in order.
+    ///
+    /// Items with the same public key share its conversion to Montgomery form and table
+    /// of multiples. The last four distinct keys stay prepared, so items need not be
+    /// grouped by signer.
+    pub fn verify_prehashed_batch<'a>(
+        items: &'a [(&'a PublicKey, &'a [u8], &'a Signature)],
+    ) -> impl Iterator<Item = bool> + 'a {
+        let mut prepared: [Option<PreparedPublicKey>; BATCH_KEYS] = Default::default();
+        let mut next = 0;
+        items
+            .iter()
+            .map(move |(public_key, prehashed_message, signature)| {
+                let index = prepared
+                    .iter()
+                    .position(|p| matches!(p, Some(p) if p.public == **public_key))
+                    .unwrap_or_else(|| {
+                        // evict the key prepared longest ago
+                        let index = next;
+                        next = (next + 1) % BATCH_KEYS;
+                        prepared[index] = Some(PreparedPublicKey::new((*public_key).clone()));
+                        index
+                    });
+                prepared[index]
+                    .as_ref()
+                    .unwrap()
+                    .verify_prehashed(prehashed_message, signature)
+            })
+    }
+
+    /// Recover the public key from a signature on message assumed to be hashed, if needed.
+    ///
+    /// The hash must be at least 16 bytes long. Returns [`Error::InvalidSignature`] if
+    /// there is no public key for this recovery id, or the signature does not verify.
+    pub fn recover_from_prehash(
+        prehashed_message: &[u8],
+        signature: &Signature,
+        recovery_id: RecoveryId,
+    ) -> Result<Self> {
+        // NB: as in `p256`, which rejects shorter hashes
+        if prehashed_message.len() < 16 {
+            return Err(Error::InvalidLength);
+        }
+
+        // R, with x-coordinate r or r + n
+        let mut x = signature.r;
+        if recovery_id.is_x_reduced() {
+            let mut carry = 0u64;
+            for (x, n) in x.iter_mut().zip(ORDER.iter()) {
+                carry += *x as u64 + *n as u64;
+                *x = carry as u32;
+                carry >>= 32;
+            }
+            if carry != 0 {
+                return Err(Error::InvalidSignature);
+            }
+        }
+        let mut y = [0u32; 8];
+        if !unsafe {
+            p256_cortex_m4_sys::P256_check_range_p(&x[0])
+                && p256_cortex_m4_sys::P256_decompress_point(
+                    &mut y[0],
+                    &x[0],
+                    recovery_id.is_y_odd() as u32,
+                )
+        } {
+            return Err(Error::InvalidSignature);
+        }
+
+        // Q = (s/r) R - (z/r) G
+        let mut z = [0u32; 8];
+        let mut r_inv = [0u32; 8];
+        let mut u1 = [0u32; 8];
+        let mut u2 = [0u32; 8];
+        let mut point = [[0u32; 8]; 3];
+        let mut base = [[0u32; 8]; 2];
+        unsafe {
+            p256_cortex_m4_sys::p256_convert_endianness(
+                &mut z[0] as *mut u32 as *mut _,
+                &bits2field(prehashed_message)[0] as *const u8 as *const _,
+                32,
+            );
+            // NB: as in `p256_verify`, the variable-time inversion is only assembled without signing
+            p256_cortex_m4_sys::P256_mod_n_inv(&mut r_inv[0], &signature.r[0]);
+            p256_cortex_m4_sys::P256_mul_mod_n(&mut u1[0], &z[0], &r_inv[0]);
+            p256_cortex_m4_sys::P256_mul_mod_n(&mut u2[0], &signature.s[0], &r_inv[0]);
+
+            // NB: `u2` is non-zero, and R is on the curve
+            p256_cortex_m4_sys::p256_scalarmult_generic(
+                &mut point[0][0],
+                &mut point[1][0],
+                &u2[0],
+                &x[0],
+                &y[0],
+            );
+            to_montgomery(&mut point[0]);
+            to_montgomery(&mut point[1]);
+            point[2] = ONE_MONTGOMERY;
+
+            // NB: `p256_scalarmult_base` rejects `u1 = 0`, in which case there is nothing to subtract
+            if p256_cortex_m4_sys::p256_scalarmult_base(&mut base[0][0], &mut base[1][0], &u1[0]) {
+                to_montgomery(&mut base[0]);
+                to_montgomery(&mut base[1]);
+                p256_cortex_m4_sys::P256_add_sub_j(point.as_mut_ptr(), base.as_ptr(), true, true);
+            }
+        }
+
+        // NB: the point at infinity comes out as (0, 0), which fails verification
+        let mut public = PublicKey {
+            x: [0u32; 8],
+            y: [0u32; 8],
+        };
+        unsafe {
+            p256_cortex_m4_sys::P256_jacobian_to_affine(
+                &mut public.x[0],
+                &mut public.y[0],
+                point.as_ptr(),
+            )
+        };
+        from_montgomery(&mut public.x);
+        from_montgomery(&mut public.y);
+        match public.verify_prehashed(prehashed_message, signature) {
+            true => Ok(public),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+}
+
+impl PreparedPublicKey {
+    /// Prepare public key for verifying many signatures.
+    pub fn new(public_key: PublicKey) -> Self {
+        let table = verify::Table::new(&public_key);
+        PreparedPublicKey {
+            public: public_key,
+            table,
+        }
+    }
+
+    /// The public key this was prepared from.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Verify signature on message assumed to be hashed, if needed.
+    ///
+    /// The result is that of [`PublicKey::verify_prehashed`].
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn verify_prehashed(&self, prehashed_message: &[u8], signature: &Signature) -> bool {
+        self.table.verify_prehashed(prehashed_message, signature)
+    }
+
+    /// Verify signature on message, which is hashed with SHA-256 first.
+    #[cfg(feature = "prehash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "prehash")))]
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let prehashed_message = sha256(message);
+        self.verify_prehashed(prehashed_message.as_ref(), signature)
+    }
+}
+
+impl From<PublicKey> for PreparedPublicKey {
+    fn from(public_key: PublicKey) -> Self {
+        Self::new(public_key)
+    }
 }
 
 impl Signature {
     /// Big-endian representation of r.
-    fn r(&self) -> [u8; 32] {
+    pub fn r(&self) -> [u8; 32] {
         let mut r = MaybeUninit::<[u8; 32]>::uninit();
         unsafe {
             p256_cortex_m4_sys::p256_convert_endianness(
@@ -341,7 +734,7 @@ impl Signature {
     }
 
     /// Big-endian representation of s.
-    fn s(&self) -> [u8; 32] {
+    pub fn s(&self) -> [u8; 32] {
         let mut s = MaybeUninit::<[u8; 32]>::uninit();
         unsafe {
             p256_cortex_m4_sys::p256_convert_endianness(
@@ -359,7 +752,7 @@ impl Signature {
     /// in the range 1..=n-1, otherwise decoding fails.
     pub fn from_untagged_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.len() != 64 {
-            return Err(Error);
+            return Err(Error::InvalidLength);
         }
 
         // NB: https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#initializing-a-struct-field-by-field
@@ -386,21 +779,29 @@ impl Signature {
             )
         };
         let valid_s =
-            unsafe { p256_cortex_m4_sys::P256_check_range_n(&signature.r[0] as *const u32) };
+            unsafe { p256_cortex_m4_sys::P256_check_range_n(&signature.s[0] as *const u32) };
 
         if valid_r && valid_s {
             Ok(signature)
         } else {
-            Err(Error)
+            Err(Error::ScalarOutOfRange)
         }
     }
 
-    // /// Decode signature from ASN.1 DER
-    // #[cfg(feature = "sec1-signatures")]
-    // #[cfg_attr(docsrs, doc(cfg(feature = "sec1-signatures")))]
-    // pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
-    //     todo!();
-    // }
+    /// Decode signature from ASN.1 DER
+    ///
+    /// This means interpreting bytes as a SEQUENCE of (unsigned) INTEGERs, as defined
+    /// under the name of `ECDSA-Sig-Value` in [SEC 1][sec-1], section C.5.
+    ///
+    /// Decoding is strict: integers must be minimally encoded, there must be no trailing
+    /// data, and r and s must be integers in the range 1..=n-1.
+    ///
+    /// [sec-1]: http://www.secg.org/sec1-v2.pdf
+    #[cfg(feature = "sec1-signatures")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sec1-signatures")))]
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_untagged_bytes(&crate::asn1::decode_signature(bytes)?)
+    }
 
     /// Encode signature from big-endian r, then big-endian s, without framing.
     pub fn to_untagged_bytes(&self) -> [u8; 64] {
@@ -419,25 +820,15 @@ impl Signature {
     #[cfg(feature = "sec1-signatures")]
     #[cfg_attr(docsrs, doc(cfg(feature = "sec1-signatures")))]
     pub fn to_sec1_bytes(&self, buffer: &mut [u8; 72]) -> usize {
-        let r = self.r();
-        let s = self.s();
-        let signature = DerSignature {
-            r: der::asn1::UintRef::new(&r).unwrap(),
-            s: der::asn1::UintRef::new(&s).unwrap(),
-        };
-
-        use der::Encode;
-        let l = signature.encode_to_slice(buffer.as_mut()).unwrap().len();
-        l
+        crate::asn1::encode_signature(&self.to_untagged_bytes(), buffer)
     }
 }
 
-#[cfg(feature = "sec1-signatures")]
-#[cfg_attr(docsrs, doc(cfg(feature = "sec1-signatures")))]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, der::Sequence)]
-struct DerSignature<'a> {
-    pub r: der::asn1::UintRef<'a>,
-    pub s: der::asn1::UintRef<'a>,
+impl Drop for Presignature {
+    fn drop(&mut self) {
+        self.0.r.zeroize();
+        self.0.k_inv.zeroize();
+    }
 }
 
 impl SharedSecret {
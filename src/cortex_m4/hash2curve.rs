@@ -0,0 +1,238 @@
+//! Hashing to the curve, following RFC 9380 with the suites `P256_XMD:SHA-256_SSWU_RO_`
+//! and `P256_XMD:SHA-256_SSWU_NU_`, and hashing to scalars.
+//!
+//! The simplified SWU map works on field elements in Montgomery form, on top of the field
+//! arithmetic of P256-Cortex-M4, and runs in constant time.
+
+use core::convert::TryInto;
+
+use p256_cortex_m4_sys::{
+    P256_add_mod_p, P256_mod_p_inv, P256_mod_p_sqrt, P256_mul_mod_p, P256_negate_mod_p_if,
+    P256_sqr_mod_p,
+};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::{from_montgomery, to_montgomery, ProjectivePoint, Scalar, ONE_MONTGOMERY};
+use crate::{Error, Result};
+
+/// Field element in Montgomery form, as little-endian words.
+type Element = [u32; 8];
+
+/// Curve coefficient A = -3.
+const A: Element = [
+    0xfffffffc, 0xffffffff, 0xffffffff, 0x00000003, 0x00000000, 0x00000000, 0x00000004, 0xfffffffc,
+];
+
+/// Curve coefficient B.
+const B: Element = [
+    0x29c4bddf, 0xd89cdf62, 0x78843090, 0xacf005cd, 0xf7212ed6, 0xe5a220ab, 0x04874834, 0xdc30061d,
+];
+
+/// Z = -10, the non-square of the simplified SWU map for P-256.
+const Z: Element = [
+    0xfffffff5, 0xffffffff, 0xffffffff, 0x0000000a, 0x00000000, 0x00000000, 0x0000000b, 0xfffffff5,
+];
+
+/// -B / A.
+const MINUS_B_OVER_A: Element = [
+    0x6341949f, 0x9d899fcb, 0x7d816585, 0x8efaac9a, 0xa7b5ba47, 0xa1e0b58e, 0x01826d67, 0xf4100209,
+];
+
+/// B / (Z * A), the x-coordinate in the exceptional case of the map.
+const B_OVER_ZA: Element = [
+    0xf0535ba9, 0x5c8dc32d, 0x8c8cf08d, 0xc17f77a9, 0x43f892a0, 0x7696788e, 0x99c03e24, 0x98680033,
+];
+
+/// 2^192.
+const TWO_192: Element = [
+    0xffffffff, 0xfffffffe, 0xfffffffe, 0xffffffff, 0x00000000, 0x00000002, 0x00000003, 0x00000000,
+];
+
+/// Prefix for hashing domain separation tags longer than 255 bytes.
+const OVERSIZE_DST_SALT: &[u8] = b"H2C-OVERSIZE-DST-";
+
+fn mul(a: &Element, b: &Element) -> Element {
+    let mut product = [0u32; 8];
+    unsafe { P256_mul_mod_p(&mut product[0], &a[0], &b[0]) };
+    product
+}
+
+fn sqr(a: &Element) -> Element {
+    let mut square = [0u32; 8];
+    unsafe { P256_sqr_mod_p(&mut square[0], &a[0]) };
+    square
+}
+
+fn add(a: &Element, b: &Element) -> Element {
+    let mut sum = [0u32; 8];
+    unsafe { P256_add_mod_p(&mut sum[0], &a[0], &b[0]) };
+    sum
+}
+
+fn eq(a: &Element, b: &Element) -> bool {
+    a.iter().zip(b.iter()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Returns `b` if `choice` is set, and `a` otherwise, in constant time.
+fn select(a: &Element, b: &Element, choice: bool) -> Element {
+    let mask = (choice as u32).wrapping_neg();
+    let mut selected = *a;
+    for (selected, b) in selected.iter_mut().zip(b.iter()) {
+        *selected ^= (*selected ^ b) & mask;
+    }
+    selected
+}
+
+/// The parity of the element in standard form, which is `sgn0` of RFC 9380.
+fn sgn0(a: &Element) -> u32 {
+    let mut standard = *a;
+    from_montgomery(&mut standard);
+    standard[0] & 1
+}
+
+/// The right-hand side x^3 + Ax + B of the curve equation.
+fn curve_equation(x: &Element) -> Element {
+    add(&mul(&add(&sqr(x), &A), x), &B)
+}
+
+/// Fills `out` with `expand_message_xmd` of RFC 9380 with SHA-256.
+///
+/// Returns [`Error::InvalidLength`] if `dst` is empty.
+pub(super) fn expand_message_xmd(msgs: &[&[u8]], dst: &[u8], out: &mut [u8]) -> Result<()> {
+    if dst.is_empty() {
+        return Err(Error::InvalidLength);
+    }
+    debug_assert!(out.len() <= 255 * 32);
+
+    let hashed_dst;
+    let dst = if dst.len() > 255 {
+        hashed_dst = Sha256::new()
+            .chain_update(OVERSIZE_DST_SALT)
+            .chain_update(dst)
+            .finalize();
+        hashed_dst.as_ref()
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let mut b_0 = Sha256::new().chain_update([0u8; 64]);
+    for msg in msgs {
+        b_0.update(msg);
+    }
+    let b_0 = b_0
+        .chain_update((out.len() as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    let mut b_i = b_0;
+    for (i, chunk) in out.chunks_mut(32).enumerate() {
+        // NB: b_1 = H(b_0 || 1 || DST'), and b_i = H((b_0 ^ b_(i-1)) || i || DST')
+        if i > 0 {
+            for (b_i, b_0) in b_i.iter_mut().zip(b_0.iter()) {
+                *b_i ^= b_0;
+            }
+        }
+        b_i = Sha256::new()
+            .chain_update(b_i)
+            .chain_update([i as u8 + 1])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        chunk.copy_from_slice(&b_i[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// Reduces a big-endian 384-bit integer modulo p, into Montgomery form.
+fn from_okm(okm: &[u8]) -> Element {
+    // NB: this is d0 * 2^192 + d1, where both halves are below 2^192 < p
+    let mut d0 = [0u32; 8];
+    let mut d1 = [0u32; 8];
+    for i in 0..6 {
+        d0[5 - i] = u32::from_be_bytes(okm[4 * i..][..4].try_into().unwrap());
+        d1[5 - i] = u32::from_be_bytes(okm[24 + 4 * i..][..4].try_into().unwrap());
+    }
+    to_montgomery(&mut d0);
+    to_montgomery(&mut d1);
+    add(&mul(&d0, &TWO_192), &d1)
+}
+
+/// Simplified SWU map of section 6.6.2 of RFC 9380.
+fn map_to_curve(u: &Element) -> ProjectivePoint {
+    // tv1 = 1 / (Z^2 u^4 + Z u^2), which is zero in the exceptional case
+    let z_u2 = mul(&Z, &sqr(u));
+    let mut tv1 = add(&sqr(&z_u2), &z_u2);
+    unsafe { P256_mod_p_inv(&mut tv1[0], &tv1[0]) };
+    let is_exceptional = eq(&tv1, &[0u32; 8]);
+
+    let x1 = select(
+        &mul(&MINUS_B_OVER_A, &add(&ONE_MONTGOMERY, &tv1)),
+        &B_OVER_ZA,
+        is_exceptional,
+    );
+    let x2 = mul(&z_u2, &x1);
+    let gx1 = curve_equation(&x1);
+    let gx2 = curve_equation(&x2);
+
+    // NB: exactly one of gx1 and gx2 is a square, so both roots are computed
+    let mut y1 = [0u32; 8];
+    let mut y2 = [0u32; 8];
+    unsafe {
+        P256_mod_p_sqrt(&mut y1[0], &gx1[0]);
+        P256_mod_p_sqrt(&mut y2[0], &gx2[0]);
+    }
+    let is_square = eq(&sqr(&y1), &gx1);
+    let x = select(&x2, &x1, is_square);
+    let mut y = select(&y2, &y1, is_square);
+
+    // NB: y is non-zero, as there are no points of order two
+    let should_negate = sgn0(u) ^ sgn0(&y);
+    let y_ptr = y.as_mut_ptr();
+    unsafe { P256_negate_mod_p_if(y_ptr, y_ptr, should_negate) };
+
+    ProjectivePoint([x, y, ONE_MONTGOMERY])
+}
+
+impl ProjectivePoint {
+    /// Hash the concatenated messages to the curve, with the suite `P256_XMD:SHA-256_SSWU_RO_` of RFC 9380.
+    ///
+    /// Returns [`Error::InvalidLength`] if the domain separation tag `dst` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash2curve")))]
+    pub fn hash_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<Self> {
+        let mut uniform_bytes = Zeroizing::new([0u8; 96]);
+        expand_message_xmd(msgs, dst, uniform_bytes.as_mut())?;
+        let q0 = map_to_curve(&from_okm(&uniform_bytes[..48]));
+        let q1 = map_to_curve(&from_okm(&uniform_bytes[48..]));
+        Ok(q0 + q1)
+    }
+
+    /// Encode the concatenated messages to the curve, with the suite `P256_XMD:SHA-256_SSWU_NU_` of RFC 9380.
+    ///
+    /// The output is not uniformly distributed, see [`ProjectivePoint::hash_to_curve`].
+    /// Returns [`Error::InvalidLength`] if the domain separation tag `dst` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash2curve")))]
+    pub fn encode_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<Self> {
+        let mut uniform_bytes = Zeroizing::new([0u8; 48]);
+        expand_message_xmd(msgs, dst, uniform_bytes.as_mut())?;
+        Ok(map_to_curve(&from_okm(uniform_bytes.as_ref())))
+    }
+}
+
+impl Scalar {
+    /// Hash the concatenated messages to a scalar, with `hash_to_field` of RFC 9380 modulo n,
+    /// using `expand_message_xmd` with SHA-256.
+    ///
+    /// The 48 uniform bytes are reduced modulo n, so the output is statistically close to uniform.
+    /// Returns [`Error::InvalidLength`] if the domain separation tag `dst` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash2curve")))]
+    pub fn hash_to_scalar(msgs: &[&[u8]], dst: &[u8]) -> Result<Self> {
+        // NB: the 48 bytes are the low end of a big-endian 512-bit integer
+        let mut uniform_bytes = Zeroizing::new([0u8; 64]);
+        expand_message_xmd(msgs, dst, &mut uniform_bytes[16..])?;
+        Ok(Scalar::from_bytes_wide(&uniform_bytes))
+    }
+}
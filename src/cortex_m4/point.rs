@@ -0,0 +1,248 @@
+//! Points on the curve.
+
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use p256_cortex_m4_sys::{
+    P256_add_sub_j, P256_double_j, P256_jacobian_to_affine, P256_negate_mod_p_if,
+    P256_point_is_on_curve,
+};
+
+use super::{from_montgomery, to_montgomery, PublicKey, Scalar, ONE_MONTGOMERY};
+use crate::{Error, Result};
+
+/// Point on the curve other than the point at infinity, for protocols beyond ECDSA and ECDH.
+///
+/// This has the same representation and encodings as a [`PublicKey`], and converts to and from it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AffinePoint(pub(super) PublicKey);
+
+/// Point on the curve, possibly the point at infinity, for adding and doubling.
+///
+/// The internal representation is in Jacobian coordinates in Montgomery form, with
+/// z-coordinate zero for the point at infinity. Operations are constant time, except
+/// if an operand is the point at infinity, or the operands are equal up to sign.
+#[derive(Clone, Copy)]
+pub struct ProjectivePoint(pub(super) [[u32; 8]; 3]);
+
+impl AffinePoint {
+    /// The base point G.
+    pub fn generator() -> Self {
+        AffinePoint(PublicKey {
+            x: [
+                0xd898c296, 0xf4a13945, 0x2deb33a0, 0x77037d81, 0x63a440f2, 0xf8bce6e5, 0xe12c4247,
+                0x6b17d1f2,
+            ],
+            y: [
+                0x37bf51f5, 0xcbb64068, 0x6b315ece, 0x2bce3357, 0x7c0f9e16, 0x8ee7eb4a, 0xfe1a7f9b,
+                0x4fe342e2,
+            ],
+        })
+    }
+
+    /// Decode from the compressed or uncompressed SEC1 encoding, see [`PublicKey::from_sec1_bytes`].
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
+        PublicKey::from_sec1_bytes(bytes).map(AffinePoint)
+    }
+
+    /// Compressed encoding: `02 || Px` if Py is even and `03 || Px` if Py is odd
+    pub fn to_compressed_sec1_bytes(&self) -> [u8; 33] {
+        self.0.to_compressed_sec1_bytes()
+    }
+
+    /// Uncompressed encoding: `04 || Px || Py`.
+    pub fn to_uncompressed_sec1_bytes(&self) -> [u8; 65] {
+        self.0.to_uncompressed_sec1_bytes()
+    }
+
+    /// Big-endian representation of x-coordinate.
+    pub fn x(&self) -> [u8; 32] {
+        self.0.x()
+    }
+
+    /// Big-endian representation of y-coordinate.
+    pub fn y(&self) -> [u8; 32] {
+        self.0.y()
+    }
+
+    /// Multiply the base point by a scalar, in constant time.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if the scalar is zero, as the result
+    /// would be the point at infinity.
+    pub fn mul_base(scalar: &Scalar) -> Result<Self> {
+        let mut point = AffinePoint(PublicKey {
+            x: [0u32; 8],
+            y: [0u32; 8],
+        });
+        if unsafe {
+            p256_cortex_m4_sys::p256_scalarmult_base(
+                &mut point.0.x[0],
+                &mut point.0.y[0],
+                &scalar.0[0],
+            )
+        } {
+            Ok(point)
+        } else {
+            Err(Error::ScalarOutOfRange)
+        }
+    }
+
+    /// Multiply this point by a scalar, in constant time.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if the scalar is zero, as the result
+    /// would be the point at infinity.
+    pub fn mul(&self, scalar: &Scalar) -> Result<Self> {
+        let mut point = AffinePoint(PublicKey {
+            x: [0u32; 8],
+            y: [0u32; 8],
+        });
+        // NB: the point is valid, so this only fails for zero
+        if unsafe {
+            p256_cortex_m4_sys::p256_scalarmult_generic(
+                &mut point.0.x[0],
+                &mut point.0.y[0],
+                &scalar.0[0],
+                &self.0.x[0],
+                &self.0.y[0],
+            )
+        } {
+            Ok(point)
+        } else {
+            Err(Error::ScalarOutOfRange)
+        }
+    }
+}
+
+impl From<PublicKey> for AffinePoint {
+    fn from(public_key: PublicKey) -> Self {
+        AffinePoint(public_key)
+    }
+}
+
+impl From<AffinePoint> for PublicKey {
+    fn from(point: AffinePoint) -> Self {
+        point.0
+    }
+}
+
+impl ProjectivePoint {
+    /// The point at infinity.
+    pub fn identity() -> Self {
+        ProjectivePoint([[0u32; 8]; 3])
+    }
+
+    /// The base point G.
+    pub fn generator() -> Self {
+        Self::from(&AffinePoint::generator())
+    }
+
+    /// Is this the point at infinity?
+    pub fn is_identity(&self) -> bool {
+        self.0[2].iter().fold(0, |acc, word| acc | word) == 0
+    }
+
+    /// Convert to affine coordinates, unless this is the point at infinity.
+    pub fn to_affine(&self) -> Option<AffinePoint> {
+        if self.is_identity() {
+            return None;
+        }
+        let mut point = AffinePoint(PublicKey {
+            x: [0u32; 8],
+            y: [0u32; 8],
+        });
+        unsafe {
+            P256_jacobian_to_affine(&mut point.0.x[0], &mut point.0.y[0], self.0.as_ptr());
+        }
+        debug_assert!(unsafe { P256_point_is_on_curve(&point.0.x[0], &point.0.y[0]) });
+        from_montgomery(&mut point.0.x);
+        from_montgomery(&mut point.0.y);
+        Some(point)
+    }
+
+    /// Double this point.
+    pub fn double(&self) -> Self {
+        let mut point = *self;
+        let jacobian = point.0.as_mut_ptr();
+        unsafe { P256_double_j(jacobian, jacobian) };
+        point
+    }
+
+    fn add_sub(mut self, other: &Self, is_sub: bool) -> Self {
+        // NB: `P256_add_sub_j` only handles the point at infinity as first operand
+        if other.is_identity() {
+            return self;
+        }
+        unsafe { P256_add_sub_j(self.0.as_mut_ptr(), other.0.as_ptr(), is_sub, false) };
+        self
+    }
+}
+
+impl From<&AffinePoint> for ProjectivePoint {
+    fn from(point: &AffinePoint) -> Self {
+        let mut projective = ProjectivePoint([point.0.x, point.0.y, ONE_MONTGOMERY]);
+        to_montgomery(&mut projective.0[0]);
+        to_montgomery(&mut projective.0[1]);
+        projective
+    }
+}
+
+impl From<AffinePoint> for ProjectivePoint {
+    fn from(point: AffinePoint) -> Self {
+        Self::from(&point)
+    }
+}
+
+impl Add for ProjectivePoint {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.add_sub(&other, false)
+    }
+}
+
+impl AddAssign for ProjectivePoint {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for ProjectivePoint {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.add_sub(&other, true)
+    }
+}
+
+impl SubAssign for ProjectivePoint {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for ProjectivePoint {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        // NB: the y-coordinate of the point at infinity may be zero, which is out of range
+        let should_negate = !self.is_identity() as u32;
+        let y = self.0[1].as_mut_ptr();
+        unsafe { P256_negate_mod_p_if(y, y, should_negate) };
+        self
+    }
+}
+
+impl PartialEq for ProjectivePoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_affine() == other.to_affine()
+    }
+}
+
+impl Eq for ProjectivePoint {}
+
+impl core::fmt::Debug for ProjectivePoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ProjectivePoint")
+            .field(&self.to_affine())
+            .finish()
+    }
+}
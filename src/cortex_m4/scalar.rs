@@ -0,0 +1,213 @@
+//! Integers modulo the order n of the base point.
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+use p256_cortex_m4_sys::{
+    P256_add_mod_n, P256_mul_mod_n, P256_negate_mod_n_if, P256_reduce_wide_mod_n,
+};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use super::{random_scalar, SecretKey};
+use crate::{Error, Result};
+
+/// Integer modulo the order n of the base point, for protocols beyond ECDSA and ECDH.
+///
+/// The internal representation is as little-endian (native) words, in the range 0..=n-1.
+/// Zeroized on drop, as scalars are usually secret.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Scalar(pub(super) [u32; 8]);
+
+/// Little-endian words of a big-endian 256-bit integer, which need not be reduced.
+fn from_be_bytes(bytes: &[u8]) -> Scalar {
+    let mut words = Scalar([0u32; 8]);
+    unsafe {
+        p256_cortex_m4_sys::p256_convert_endianness(
+            &mut words.0[0] as *mut u32 as *mut _,
+            &bytes[0] as *const u8 as *const _,
+            32,
+        )
+    };
+    words
+}
+
+impl Scalar {
+    /// Zero.
+    pub const ZERO: Self = Scalar([0u32; 8]);
+
+    /// One.
+    pub const ONE: Self = Scalar([1, 0, 0, 0, 0, 0, 0, 0]);
+
+    /// Generate a random `Scalar` in the range 1..=n-1.
+    ///
+    /// The implementation uses rejection sampling, exactly like [`SecretKey::random`].
+    pub fn random(rng: impl CryptoRng + RngCore) -> Self {
+        let mut scalar = Scalar([0u32; 8]);
+        let mut rng = rng;
+        random_scalar(&mut rng, &mut scalar.0);
+        scalar
+    }
+
+    /// Verifies that there are 32 bytes that correspond to a big-endian integer in the range 0..=n-1.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        let bytes = bytes.as_ref();
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+
+        let scalar = from_be_bytes(bytes);
+        // NB: `P256_check_range_n` excludes zero
+        if !scalar.is_zero() && !unsafe { p256_cortex_m4_sys::P256_check_range_n(&scalar.0[0]) } {
+            return Err(Error::ScalarOutOfRange);
+        }
+        Ok(scalar)
+    }
+
+    /// Big-endian representation as 32 bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut big_endian = [0u8; 32];
+        unsafe {
+            p256_cortex_m4_sys::p256_convert_endianness(
+                &mut big_endian[0] as *mut u8 as *mut _,
+                &self.0[0] as *const u32 as *const _,
+                32,
+            )
+        };
+        big_endian
+    }
+
+    /// Reduces a big-endian 512-bit integer modulo n.
+    ///
+    /// For uniformly random input, the output is statistically close to uniform.
+    pub fn from_bytes_wide(bytes: &[u8; 64]) -> Self {
+        let mut wide = Zeroizing::new([0u32; 16]);
+        let mut scalar = Scalar([0u32; 8]);
+        unsafe {
+            p256_cortex_m4_sys::p256_convert_endianness(
+                &mut wide[0] as *mut u32 as *mut _,
+                &bytes[0] as *const u8 as *const _,
+                64,
+            );
+            P256_reduce_wide_mod_n(&mut scalar.0[0], &wide[0]);
+        }
+        scalar
+    }
+
+    /// Is this zero?
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().fold(0, |acc, word| acc | word) == 0
+    }
+
+    /// Multiplicative inverse, in constant time.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if this is zero.
+    pub fn invert(&self) -> Result<Self> {
+        if self.is_zero() {
+            return Err(Error::ScalarOutOfRange);
+        }
+        let mut inverse = Scalar([0u32; 8]);
+        unsafe { p256_cortex_m4_sys::P256_mod_n_inv(&mut inverse.0[0], &self.0[0]) };
+        Ok(inverse)
+    }
+
+    /// Multiplicative inverse, in variable time.
+    ///
+    /// Only use this for public scalars. Returns [`Error::ScalarOutOfRange`] if this is zero.
+    ///
+    /// On this backend, this is [`Scalar::invert`]: the variable-time inversion of
+    /// P256-Cortex-M4 is only assembled without signing.
+    pub fn invert_vartime(&self) -> Result<Self> {
+        self.invert()
+    }
+}
+
+impl From<&SecretKey> for Scalar {
+    fn from(secret_key: &SecretKey) -> Self {
+        Scalar(secret_key.0)
+    }
+}
+
+impl Add<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: &Scalar) -> Scalar {
+        let mut sum = Scalar([0u32; 8]);
+        unsafe { P256_add_mod_n(&mut sum.0[0], &self.0[0], &other.0[0]) };
+        sum
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: Scalar) -> Scalar {
+        &self + &other
+    }
+}
+
+impl Sub<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: &Scalar) -> Scalar {
+        self + &-other
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: Scalar) -> Scalar {
+        &self - &other
+    }
+}
+
+impl Mul<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: &Scalar) -> Scalar {
+        let mut product = Scalar([0u32; 8]);
+        unsafe { P256_mul_mod_n(&mut product.0[0], &self.0[0], &other.0[0]) };
+        product
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: Scalar) -> Scalar {
+        &self * &other
+    }
+}
+
+impl Neg for &Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        // NB: zero would be negated to n, which is out of range
+        let should_negate = !self.is_zero() as u32;
+        let mut negation = Scalar([0u32; 8]);
+        unsafe { P256_negate_mod_n_if(&mut negation.0[0], &self.0[0], should_negate) };
+        negation
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        -&self
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        // NB: in constant time, as scalars are usually secret
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+impl Eq for Scalar {}
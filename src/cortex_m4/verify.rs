@@ -0,0 +1,84 @@
+//! ECDSA verification against a precomputed table for the public key.
+//!
+//! This is `p256_verify` from `p256-cortex-m4.c`, split so that the conversion of the
+//! public key to Montgomery form, the check that it is on the curve, and its table of
+//! odd multiples are done once per key instead of once per signature.
+//! The table of odd multiples of the base point is the one `p256_verify` uses.
+
+use p256_cortex_m4_sys::{P256_add_sub_j, P256_basepoint_precomp, P256_double_j};
+
+use super::{to_montgomery, PublicKey, Signature, ONE_MONTGOMERY};
+use crate::{bits2field, window::slide};
+
+/// Little-endian representation of an integer in little-endian words.
+fn to_le_bytes(words: &[u32; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, word) in words.iter().enumerate() {
+        bytes[4 * i..][..4].copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+/// P, 3P, 5P, ..., 15P for a public key P, in Jacobian coordinates in Montgomery form.
+#[derive(Clone)]
+pub(crate) struct Table([[[u32; 8]; 3]; 8]);
+
+impl Table {
+    /// `PublicKey` is always on the curve, so there is nothing to check.
+    pub(crate) fn new(public_key: &PublicKey) -> Self {
+        let mut point = [public_key.x, public_key.y, ONE_MONTGOMERY];
+        to_montgomery(&mut point[0]);
+        to_montgomery(&mut point[1]);
+
+        let mut double = [[0u32; 8]; 3];
+        unsafe { P256_double_j(double.as_mut_ptr(), point.as_ptr()) };
+
+        let mut table = Table([point; 8]);
+        for i in 1..8 {
+            let (previous, rest) = table.0.split_at_mut(i);
+            rest[0] = double;
+            unsafe { P256_add_sub_j(rest[0].as_mut_ptr(), previous[i - 1].as_ptr(), false, false) };
+        }
+        table
+    }
+
+    /// Verify signature on message assumed to be hashed, if needed.
+    pub(crate) fn verify_prehashed(&self, prehashed_message: &[u8], signature: &Signature) -> bool {
+        // NB: `Signature` is always in range, and everything is public
+        let mut z = [0u32; 8];
+        let mut w = [0u32; 8];
+        let mut u1 = [0u32; 8];
+        let mut u2 = [0u32; 8];
+        unsafe {
+            p256_cortex_m4_sys::p256_convert_endianness(
+                &mut z[0] as *mut u32 as *mut _,
+                &bits2field(prehashed_message)[0] as *const u8 as *const _,
+                32,
+            );
+            // NB: as in `p256_verify`, the variable-time inversion is only assembled without signing
+            p256_cortex_m4_sys::P256_mod_n_inv(&mut w[0], &signature.s[0]);
+            p256_cortex_m4_sys::P256_mul_mod_n(&mut u1[0], &z[0], &w[0]);
+            p256_cortex_m4_sys::P256_mul_mod_n(&mut u2[0], &signature.r[0], &w[0]);
+        }
+        let slide_base = slide(&to_le_bytes(&u1));
+        let slide_key = slide(&to_le_bytes(&u2));
+
+        let base_table = unsafe { &*P256_basepoint_precomp };
+        let mut point = [[0u32; 8]; 3];
+        let point = point.as_mut_ptr();
+        for (base, key) in slide_base.iter().zip(slide_key.iter()).rev() {
+            unsafe {
+                P256_double_j(point, point);
+                if *base != 0 {
+                    let multiple = &base_table[(base.unsigned_abs() / 2) as usize];
+                    P256_add_sub_j(point, multiple.as_ptr(), *base < 0, true);
+                }
+                if *key != 0 {
+                    let multiple = &self.0[(key.unsigned_abs() / 2) as usize];
+                    P256_add_sub_j(point, multiple.as_ptr(), *key < 0, false);
+                }
+            }
+        }
+        unsafe { p256_cortex_m4_sys::P256_verify_last_step(&signature.r[0], point) }
+    }
+}
@@ -0,0 +1,393 @@
+//! [COSE][rfc-9052] structures with ES256.
+//!
+//! Keys are encoded as `COSE_Key` maps of [RFC 9053][rfc-9053], section 7.1.1, with
+//! the deterministic encoding of CBOR: labels are sorted, and all lengths are definite
+//! and minimal. Decoding is strict, and rejects anything else.
+//!
+//! Messages are signed and verified as [`Sign1`], without allocation.
+//!
+//! [rfc-9052]: https://www.rfc-editor.org/rfc/rfc9052
+//! [rfc-9053]: https://www.rfc-editor.org/rfc/rfc9053
+
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::{
+    cbor::{self, Decoder, Encoder, ARRAY, BYTES, MAP, SIMPLE, TAG, TEXT},
+    Error, PublicKey, Result, SecretKey, Signature,
+};
+
+/// Algorithm identifier of ECDSA with SHA-256.
+pub const ES256: i64 = -7;
+
+/// Key type of elliptic curve keys with x- and y-coordinate.
+const EC2: i64 = 2;
+/// Elliptic curve identifier of P-256.
+const P256: i64 = 1;
+
+// labels of COSE_Key, in deterministic order
+const KTY: i64 = 1;
+const KID: i64 = 2;
+const ALG: i64 = 3;
+const CRV: i64 = -1;
+const X: i64 = -2;
+const Y: i64 = -3;
+const D: i64 = -4;
+
+/// Length of [`PublicKey::to_cose_key`].
+const PUBLIC_KEY_LEN: usize = 77;
+/// Length of [`SecretKey::to_cose_key`].
+const SECRET_KEY_LEN: usize = 112;
+
+/// The y-coordinate, or its sign for compressed points.
+enum Coordinate<'a> {
+    Bytes(&'a [u8]),
+    Sign(bool),
+}
+
+/// Parameters of an EC2 `COSE_Key` on P-256; `kid` is accepted but ignored.
+struct CoseKey<'a> {
+    x: Option<&'a [u8]>,
+    y: Option<Coordinate<'a>>,
+    d: Option<&'a [u8]>,
+}
+
+impl<'a> CoseKey<'a> {
+    fn decode(bytes: &'a [u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes);
+        let mut key = CoseKey {
+            x: None,
+            y: None,
+            d: None,
+        };
+        let (mut kty, mut crv) = (None, None);
+
+        let mut previous = &[][..];
+        for _ in 0..decoder.expect(MAP)? {
+            match decoder.label(&mut previous)? {
+                KTY => kty = Some(decoder.int()?),
+                KID => {
+                    decoder.bytes()?;
+                }
+                ALG => {
+                    if decoder.int()? != ES256 {
+                        return Err(Error::UnsupportedAlgorithm);
+                    }
+                }
+                CRV => crv = Some(decoder.int()?),
+                X => key.x = Some(decoder.bytes()?),
+                Y => {
+                    key.y = Some(match decoder.peek() {
+                        Some(SIMPLE) => Coordinate::Sign(decoder.bool()?),
+                        _ => Coordinate::Bytes(decoder.bytes()?),
+                    })
+                }
+                D => key.d = Some(decoder.bytes()?),
+                _ => return Err(Error::InvalidEncoding),
+            }
+        }
+        decoder.finish()?;
+
+        if kty != Some(EC2) || crv != Some(P256) {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+        Ok(key)
+    }
+
+    fn public_key(&self) -> Result<PublicKey> {
+        let (Some(x), Some(y)) = (self.x, self.y.as_ref()) else {
+            return Err(Error::InvalidEncoding);
+        };
+        if x.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+        let mut sec1 = [0u8; 65];
+        sec1[1..33].copy_from_slice(x);
+        match y {
+            Coordinate::Bytes(y) if y.len() == 32 => {
+                sec1[0] = 0x04;
+                sec1[33..].copy_from_slice(y);
+                PublicKey::from_sec1_bytes(&sec1)
+            }
+            Coordinate::Bytes(_) => Err(Error::InvalidLength),
+            Coordinate::Sign(odd) => {
+                sec1[0] = 0x02 | *odd as u8;
+                PublicKey::from_sec1_bytes(&sec1[..33])
+            }
+        }
+    }
+}
+
+/// Encodes kty, alg, crv, x, y, and optionally d, returning the length.
+fn encode(public_key: &PublicKey, d: Option<&[u8; 32]>, buffer: &mut [u8]) -> Result<usize> {
+    let mut encoder = Encoder::new(buffer);
+    encoder.head(MAP, 5 + d.is_some() as u64)?;
+    encoder.int(KTY)?;
+    encoder.int(EC2)?;
+    encoder.int(ALG)?;
+    encoder.int(ES256)?;
+    encoder.int(CRV)?;
+    encoder.int(P256)?;
+    encoder.int(X)?;
+    encoder.bytes(&public_key.x())?;
+    encoder.int(Y)?;
+    encoder.bytes(&public_key.y())?;
+    if let Some(d) = d {
+        encoder.int(D)?;
+        encoder.bytes(d)?;
+    }
+    Ok(encoder.len())
+}
+
+impl PublicKey {
+    /// Decode from an EC2 `COSE_Key` on P-256, in deterministically encoded CBOR.
+    ///
+    /// The y-coordinate may be a sign bit, for compressed points. An `alg`, if present,
+    /// must be [`ES256`], a `kid` is ignored, as is a private key `d`. Other labels are
+    /// rejected.
+    #[cfg_attr(docsrs, doc(cfg(feature = "cose")))]
+    pub fn from_cose_key(bytes: &[u8]) -> Result<Self> {
+        CoseKey::decode(bytes)?.public_key()
+    }
+
+    /// Encode as EC2 `COSE_Key` with `kty`, `alg` ([`ES256`]), `crv`, `x`, and `y`.
+    ///
+    /// Returns the length of the encoding, which is always 77.
+    #[cfg_attr(docsrs, doc(cfg(feature = "cose")))]
+    pub fn to_cose_key(&self, buffer: &mut [u8; PUBLIC_KEY_LEN]) -> usize {
+        encode(self, None, buffer).unwrap()
+    }
+}
+
+impl SecretKey {
+    /// Decode from an EC2 `COSE_Key` on P-256, in deterministically encoded CBOR.
+    ///
+    /// The private key `d` is required, the public key `(x, y)` is optional,
+    /// but must match if present. Otherwise as [`PublicKey::from_cose_key`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "cose")))]
+    pub fn from_cose_key(bytes: &[u8]) -> Result<Self> {
+        let key = CoseKey::decode(bytes)?;
+        let secret_key = Self::from_bytes(key.d.ok_or(Error::InvalidEncoding)?)?;
+        if key.x.is_some() || key.y.is_some() {
+            let public_key = key.public_key()?;
+            if public_key.to_untagged_bytes() != secret_key.public_key().to_untagged_bytes() {
+                return Err(Error::KeyMismatch);
+            }
+        }
+        Ok(secret_key)
+    }
+
+    /// Encode as EC2 `COSE_Key` with `kty`, `alg` ([`ES256`]), `crv`, `x`, `y`, and `d`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "cose")))]
+    pub fn to_cose_key(&self) -> Zeroizing<[u8; SECRET_KEY_LEN]> {
+        let d = Zeroizing::new(unsafe { self.to_bytes() });
+        let mut cose_key = Zeroizing::new([0u8; SECRET_KEY_LEN]);
+        encode(&self.public_key(), Some(&d), cose_key.as_mut()).unwrap();
+        cose_key
+    }
+}
+
+// labels of header parameters
+const HEADER_ALG: i64 = 1;
+const HEADER_CRIT: i64 = 2;
+const HEADER_KID: i64 = 4;
+
+/// Tag of `COSE_Sign1_Tagged`.
+const SIGN1_TAG: u64 = 18;
+/// The protected header `{alg: ES256}` of signed messages.
+const PROTECTED: [u8; 3] = [0xa1, 0x01, 0x26];
+
+/// A `COSE_Sign1` message of [RFC 9052][rfc-9052], section 4.2, signed with ES256.
+///
+/// To sign, build with [`Sign1::new`] and call [`Sign1::sign`], which writes the
+/// `COSE_Sign1_Tagged` message into a buffer. To verify, [`Sign1::decode`] a tagged or
+/// untagged message and call [`Sign1::verify`]. The external AAD is not part of the
+/// message, signer and verifier must both set it with [`Sign1::external_aad`].
+///
+/// [rfc-9052]: https://www.rfc-editor.org/rfc/rfc9052
+#[derive(Clone, Debug)]
+pub struct Sign1<'a> {
+    protected: &'a [u8],
+    kid: Option<&'a [u8]>,
+    payload: Option<&'a [u8]>,
+    detached: bool,
+    external_aad: &'a [u8],
+    signature: Option<Signature>,
+}
+
+impl<'a> Sign1<'a> {
+    /// Message to sign, with protected header `{alg: ES256}`.
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self {
+            protected: &PROTECTED,
+            kid: None,
+            payload: Some(payload),
+            detached: false,
+            external_aad: &[],
+            signature: None,
+        }
+    }
+
+    /// Set the key identifier, in the unprotected header.
+    pub fn kid(mut self, kid: &'a [u8]) -> Self {
+        self.kid = Some(kid);
+        self
+    }
+
+    /// Sign the payload, but do not include it in the message.
+    pub fn detached(mut self) -> Self {
+        self.detached = true;
+        self
+    }
+
+    /// Set the external additional authenticated data.
+    pub fn external_aad(mut self, external_aad: &'a [u8]) -> Self {
+        self.external_aad = external_aad;
+        self
+    }
+
+    /// Sign the message, writing `COSE_Sign1_Tagged` into `buffer`.
+    ///
+    /// Returns the length of the message, or [`Error::InvalidLength`] if the buffer
+    /// is too small.
+    pub fn sign(
+        &self,
+        secret_key: &SecretKey,
+        rng: impl CryptoRng + RngCore,
+        buffer: &mut [u8],
+    ) -> Result<usize> {
+        // decoded messages with detached payload cannot be signed again
+        let payload = self.payload.ok_or(Error::InvalidEncoding)?;
+        let signature = secret_key.sign_prehashed(&self.digest(payload), rng);
+
+        let mut encoder = Encoder::new(buffer);
+        encoder.head(TAG, SIGN1_TAG)?;
+        encoder.head(ARRAY, 4)?;
+        encoder.bytes(self.protected)?;
+        match self.kid {
+            Some(kid) => {
+                encoder.head(MAP, 1)?;
+                encoder.int(HEADER_KID)?;
+                encoder.bytes(kid)?;
+            }
+            None => encoder.head(MAP, 0)?,
+        }
+        match self.detached {
+            true => encoder.null()?,
+            false => encoder.bytes(payload)?,
+        }
+        encoder.bytes(&signature.to_untagged_bytes())?;
+        Ok(encoder.len())
+    }
+
+    /// Decode a `COSE_Sign1` message, or `COSE_Sign1_Tagged`.
+    ///
+    /// The protected header must contain `alg` [`ES256`], and no `crit`. Header maps must
+    /// be deterministically encoded. The signature must be the 64 byte concatenation of
+    /// `r` and `s`, as in [`Signature::from_untagged_bytes`]; DER is rejected.
+    pub fn decode(bytes: &'a [u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes);
+        if decoder.peek() == Some(TAG) && decoder.expect(TAG)? != SIGN1_TAG {
+            return Err(Error::InvalidEncoding);
+        }
+        if decoder.expect(ARRAY)? != 4 {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let protected = decoder.bytes()?;
+        let mut alg = None;
+        let mut header = Decoder::new(protected);
+        if !protected.is_empty() {
+            let mut previous = &[][..];
+            for _ in 0..header.expect(MAP)? {
+                match header.label(&mut previous)? {
+                    HEADER_ALG => alg = Some(header.int()?),
+                    HEADER_CRIT => return Err(Error::InvalidEncoding),
+                    _ => header.skip()?,
+                }
+            }
+        }
+        header.finish()?;
+        if alg != Some(ES256) {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+
+        let mut kid = None;
+        let mut previous = &[][..];
+        for _ in 0..decoder.expect(MAP)? {
+            match decoder.label(&mut previous)? {
+                HEADER_ALG | HEADER_CRIT => return Err(Error::InvalidEncoding),
+                HEADER_KID => kid = Some(decoder.bytes()?),
+                _ => decoder.skip()?,
+            }
+        }
+
+        let payload = decoder.null_or_bytes()?;
+        let signature = decoder.bytes()?;
+        if signature.len() != 64 {
+            return Err(Error::InvalidLength);
+        }
+        let signature = Signature::from_untagged_bytes(signature)?;
+        decoder.finish()?;
+
+        Ok(Self {
+            protected,
+            kid,
+            payload,
+            detached: payload.is_none(),
+            external_aad: &[],
+            signature: Some(signature),
+        })
+    }
+
+    /// The key identifier of the unprotected header, if any.
+    pub fn key_id(&self) -> Option<&'a [u8]> {
+        self.kid
+    }
+
+    /// The payload, unless detached.
+    pub fn payload(&self) -> Option<&'a [u8]> {
+        match self.detached {
+            true => None,
+            false => self.payload,
+        }
+    }
+
+    /// The signature of a decoded message.
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    /// Verify the signature of a decoded message, with its payload.
+    ///
+    /// Returns false for messages with detached payload, or without signature.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        match self.payload() {
+            Some(payload) => self.verify_detached(payload, public_key),
+            None => false,
+        }
+    }
+
+    /// Verify the signature of a decoded message, with a detached payload.
+    pub fn verify_detached(&self, payload: &[u8], public_key: &PublicKey) -> bool {
+        match &self.signature {
+            Some(signature) => public_key.verify_prehashed(&self.digest(payload), signature),
+            None => false,
+        }
+    }
+
+    /// Hash of `Sig_structure = ["Signature1", protected, external_aad, payload]`.
+    fn digest(&self, payload: &[u8]) -> [u8; 32] {
+        let mut hash = Sha256::new();
+        let mut head = [0u8; 9];
+        hash.update(cbor::head(ARRAY, 4, &mut head));
+        hash.update(cbor::head(TEXT, 10, &mut head));
+        hash.update(b"Signature1");
+        for bytes in [self.protected, self.external_aad, payload] {
+            hash.update(cbor::head(BYTES, bytes.len() as u64, &mut head));
+            hash.update(bytes);
+        }
+        hash.finalize().into()
+    }
+}
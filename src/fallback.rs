@@ -1,31 +1,51 @@
 use core::convert::TryInto;
 
-use ecdsa::signature::hazmat::{PrehashVerifier, RandomizedPrehashSigner};
-use elliptic_curve::sec1::ToEncodedPoint;
+use ecdsa::hazmat::{SignPrimitive, VerifyPrimitive};
+use elliptic_curve::{
+    ff::Field,
+    ops::{Invert, Reduce},
+    point::AffineCoordinates,
+    sec1::ToEncodedPoint,
+};
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "rfc6979")]
+use zeroize::Zeroizing;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::{Error, Result};
+#[cfg(feature = "rfc6979")]
+use crate::nonce::Nonces;
+use crate::{bits2field, Error, RecoveryId, Result};
 
-impl From<elliptic_curve::Error> for Error {
-    fn from(_: elliptic_curve::Error) -> Self {
-        Self
-    }
-}
+#[cfg(feature = "hash2curve")]
+mod hash2curve;
+mod point;
+mod scalar;
+mod verify;
 
-impl From<p256::ecdsa::Error> for Error {
-    fn from(_: p256::ecdsa::Error) -> Self {
-        Self
-    }
-}
+pub use point::{AffinePoint, ProjectivePoint};
+pub use scalar::Scalar;
 
 /// NIST P-256 secret key.
 #[derive(Clone)]
 pub struct SecretKey(p256::SecretKey);
 
 /// NIST P-256 public key.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PublicKey(p256::PublicKey);
 
+/// Number of distinct public keys whose preparation [`PublicKey::verify_prehashed_batch`] keeps.
+const BATCH_KEYS: usize = 4;
+
+/// NIST P-256 public key, prepared for verifying many signatures.
+///
+/// The tables of odd multiples of the base point and the key are computed once,
+/// instead of once per signature in [`PublicKey::verify_prehashed`].
+#[derive(Clone)]
+pub struct PreparedPublicKey {
+    public: PublicKey,
+    table: verify::Table,
+}
+
 /// NIST P-256 keypair.
 #[derive(Clone)]
 pub struct Keypair {
@@ -39,15 +59,28 @@ pub struct Keypair {
 #[derive(Clone, Debug)]
 pub struct Signature(p256::ecdsa::Signature);
 
+/// Precomputed `(r, k^-1)` for a single ECDSA signature.
+///
+/// Obtained from [`SecretKey::presign`], consumed by [`SecretKey::sign_prehashed_with`].
+/// Zeroized on drop.
+pub struct Presignature {
+    r: p256::Scalar,
+    k_inv: p256::Scalar,
+}
+
 /// Outcome of ECDH key agreement.
-pub struct SharedSecret(p256::ecdh::SharedSecret);
+///
+/// The x-coordinate of the multiplication of a secret key and a public key,
+/// represented as big-endian integer.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct SharedSecret([u8; 32]);
 
 impl Keypair {
     /// Generate a random `Keypair`.
     ///
     /// The implementation uses rejection sampling.
-    pub fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
-        let secret = SecretKey(p256::SecretKey::random(rng));
+    pub fn random(rng: impl CryptoRng + RngCore) -> Self {
+        let secret = SecretKey::random(rng);
         let public = secret.public_key();
 
         Keypair { public, secret }
@@ -58,16 +91,28 @@ impl SecretKey {
     /// Generate a random `SecretKey`.
     ///
     /// The implementation uses rejection sampling.
-    pub fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
-        SecretKey(p256::SecretKey::random(rng))
+    pub fn random(rng: impl CryptoRng + RngCore) -> Self {
+        let mut rng = rng;
+        SecretKey(p256::SecretKey::random(&mut rng))
     }
 
     /// Verifies that there are 32 bytes that correspond to a big-endian integer in the range 1..=n-1.
     pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
-        Ok(SecretKey(p256::SecretKey::from_slice(bytes.as_ref())?))
+        let bytes = bytes.as_ref();
+        // NB: `p256::SecretKey::from_slice` would also accept (and pad) 24 to 31 bytes
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+        Ok(SecretKey(
+            p256::SecretKey::from_slice(bytes).map_err(|_| Error::ScalarOutOfRange)?,
+        ))
     }
 
     /// Return secret scalar as big-endian integer.
+    ///
+    /// # Safety
+    ///
+    /// "unsafe" because the caller is responsible for keeping the value secret.
     pub unsafe fn to_bytes(&self) -> [u8; 32] {
         let mut big_endian = [0u8; 32];
         big_endian.copy_from_slice(&self.0.to_bytes());
@@ -79,37 +124,173 @@ impl SecretKey {
         PublicKey(self.0.public_key())
     }
 
-    /// Attempt at unraveling the traits in `p256`.
+    /// Non-deterministic signature on message assumed to be hashed, if needed.
+    ///
+    /// Internally, draws 256-bit `k` repeatedly, until signing succeeds.
     pub fn sign_prehashed(
         &self,
         prehashed_message: &[u8],
-        mut rng: impl CryptoRng + RngCore,
+        rng: impl CryptoRng + RngCore,
     ) -> Signature {
-        let signing_key: p256::ecdsa::SigningKey = (&self.0).into();
+        let mut rng = rng;
+        loop {
+            if let Ok(signature) =
+                self.sign_prehashed_with(self.presign(&mut rng), prehashed_message)
+            {
+                return signature;
+            }
+        }
+    }
 
-        Signature(
-            signing_key
-                .sign_prehash_with_rng(&mut rng, prehashed_message)
-                .unwrap(),
+    #[cfg(feature = "rfc6979")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rfc6979")))]
+    /// Deterministic signature on message assumed to be hashed, if needed.
+    ///
+    /// Internally, draws `k` from the HMAC-DRBG of [RFC 6979][rfc-6979], until signing succeeds.
+    ///
+    /// [rfc-6979]: https://datatracker.ietf.org/doc/html/rfc6979
+    pub fn sign_prehashed_deterministic(&self, prehashed_message: &[u8]) -> Signature {
+        let secret = Zeroizing::new(unsafe { self.to_bytes() });
+        self.sign_prehashed_with_nonces(
+            prehashed_message,
+            Nonces::new(&secret, prehashed_message, &[]),
         )
     }
 
+    #[cfg(feature = "rfc6979")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rfc6979")))]
+    /// Hedged signature on message assumed to be hashed, if needed.
+    ///
+    /// Internally, draws `k` from the HMAC-DRBG of [RFC 6979][rfc-6979], seeded with additional
+    /// randomness from `rng` as in section 3.6, until signing succeeds. This protects against
+    /// both a weak RNG and fault attacks on purely deterministic signatures, see also
+    /// [draft-irtf-cfrg-det-sigs-with-noise][noise].
+    ///
+    /// [rfc-6979]: https://datatracker.ietf.org/doc/html/rfc6979
+    /// [noise]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-det-sigs-with-noise/
+    pub fn sign_prehashed_hedged(
+        &self,
+        prehashed_message: &[u8],
+        rng: impl CryptoRng + RngCore,
+    ) -> Signature {
+        let mut rng = rng;
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(entropy.as_mut());
+        let secret = Zeroizing::new(unsafe { self.to_bytes() });
+        self.sign_prehashed_with_nonces(
+            prehashed_message,
+            Nonces::new(&secret, prehashed_message, entropy.as_ref()),
+        )
+    }
+
+    /// First step of a two-step signature, which does not depend on the message.
+    ///
+    /// This accounts for nearly all of the time spent signing.
+    /// Internally, draws 256-bit `k` repeatedly, until the precomputation succeeds.
+    pub fn presign(&self, rng: impl CryptoRng + RngCore) -> Presignature {
+        let mut rng = rng;
+        loop {
+            let k = p256::NonZeroScalar::random(&mut rng);
+            let x = (p256::ProjectivePoint::GENERATOR * *k).to_affine().x();
+            let r = <p256::Scalar as Reduce<p256::U256>>::reduce_bytes(&x);
+            if !bool::from(r.is_zero()) {
+                let k_inv = *k.invert();
+                return Presignature { r, k_inv };
+            }
+        }
+    }
+
+    /// Second step of a two-step signature on message assumed to be hashed, if needed.
+    ///
+    /// With negligible probability, this fails, and signing must start over with a new
+    /// presignature.
+    pub fn sign_prehashed_with(
+        &self,
+        presignature: Presignature,
+        prehashed_message: &[u8],
+    ) -> Result<Signature> {
+        let z = <p256::Scalar as Reduce<p256::U256>>::reduce_bytes(
+            &bits2field(prehashed_message).into(),
+        );
+        let d = self.0.to_nonzero_scalar();
+        let s = presignature.k_inv * (z + presignature.r * *d);
+        Ok(Signature(
+            p256::ecdsa::Signature::from_scalars(presignature.r, s)
+                .map_err(|_| Error::SigningFailed)?,
+        ))
+    }
+
+    /// Non-deterministic signature on message assumed to be hashed, if needed, together
+    /// with the [`RecoveryId`] to recover the public key from it.
+    ///
+    /// Internally, draws 256-bit `k` repeatedly, until signing succeeds.
+    pub fn sign_prehashed_recoverable(
+        &self,
+        prehashed_message: &[u8],
+        rng: impl CryptoRng + RngCore,
+    ) -> (Signature, RecoveryId) {
+        let z = bits2field(prehashed_message);
+        let d = self.0.to_nonzero_scalar();
+        let mut rng = rng;
+        loop {
+            let k = p256::NonZeroScalar::random(&mut rng);
+            if let Ok((signature, Some(recovery_id))) = d.as_ref().try_sign_prehashed(*k, &z.into())
+            {
+                let recovery_id =
+                    RecoveryId::new(recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+                return (Signature(signature), recovery_id);
+            }
+        }
+    }
+
+    #[cfg(feature = "rfc6979")]
+    fn sign_prehashed_with_nonces(
+        &self,
+        prehashed_message: &[u8],
+        mut nonces: Nonces,
+    ) -> Signature {
+        use elliptic_curve::ff::PrimeField;
+        let z = bits2field(prehashed_message);
+        let d = self.0.to_nonzero_scalar();
+        loop {
+            let k = nonces.next();
+            let k = Option::<p256::Scalar>::from(p256::Scalar::from_repr((*k).into()));
+            if let Some(Ok((signature, _))) = k.map(|k| d.as_ref().try_sign_prehashed(k, &z.into()))
+            {
+                return Signature(signature);
+            }
+        }
+    }
+
     #[cfg(feature = "prehash")]
     #[cfg_attr(docsrs, doc(cfg(feature = "prehash")))]
+    /// Non-deterministic signature on message, which is hashed with SHA-256 first.
+    pub fn sign(&self, message: &[u8], rng: impl CryptoRng + RngCore) -> Signature {
+        let prehashed_message = crate::sha256(message);
+        self.sign_prehashed(prehashed_message.as_ref(), rng)
+    }
+
+    #[cfg(all(feature = "prehash", feature = "rfc6979"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "prehash", feature = "rfc6979"))))]
     /// Deterministic signature on message, which is hashed with SHA-256 first.
-    pub fn sign(&self, message: &[u8], _rng: impl CryptoRng + RngCore) -> Signature {
-        let signer: p256::ecdsa::SigningKey = self.0.clone().into();
-        use p256::ecdsa::signature::Signer;
-        let signature = signer.sign(message);
-        Signature(signature)
+    pub fn sign_deterministic(&self, message: &[u8]) -> Signature {
+        let prehashed_message = crate::sha256(message);
+        self.sign_prehashed_deterministic(prehashed_message.as_ref())
+    }
+
+    #[cfg(all(feature = "prehash", feature = "rfc6979"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "prehash", feature = "rfc6979"))))]
+    /// Hedged signature on message, which is hashed with SHA-256 first.
+    pub fn sign_hedged(&self, message: &[u8], rng: impl CryptoRng + RngCore) -> Signature {
+        let prehashed_message = crate::sha256(message);
+        self.sign_prehashed_hedged(prehashed_message.as_ref(), rng)
     }
 
     /// ECDH key agreement.
     pub fn agree(&self, other: &PublicKey) -> SharedSecret {
-        SharedSecret(elliptic_curve::ecdh::diffie_hellman(
-            self.0.to_nonzero_scalar(),
-            other.0.as_affine(),
-        ))
+        let shared =
+            elliptic_curve::ecdh::diffie_hellman(self.0.to_nonzero_scalar(), other.0.as_affine());
+        SharedSecret((*shared.raw_secret_bytes()).into())
     }
 }
 
@@ -119,7 +300,7 @@ impl PublicKey {
     /// In other words, the uncompressed SEC1 format, without the leading 0x04 byte tag.
     pub fn from_untagged_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.len() != 64 {
-            return Err(Error);
+            return Err(Error::InvalidLength);
         }
         let mut sec1_bytes = [4u8; 65];
         sec1_bytes[1..].copy_from_slice(bytes);
@@ -133,7 +314,15 @@ impl PublicKey {
     ///
     /// [sec-1]: http://www.secg.org/sec1-v2.pdf
     pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
-        Ok(PublicKey(p256::PublicKey::from_sec1_bytes(bytes)?))
+        // NB: `p256::PublicKey::from_sec1_bytes` would also accept the identity
+        match (bytes.first(), bytes.len()) {
+            (Some(2 | 3), 33) | (Some(4), 65) => {}
+            (Some(2..=4) | None, _) => return Err(Error::InvalidLength),
+            _ => return Err(Error::InvalidEncoding),
+        }
+        Ok(PublicKey(
+            p256::PublicKey::from_sec1_bytes(bytes).map_err(|_| Error::PointNotOnCurve)?,
+        ))
     }
 
     /// Raw encoding, x-coordinate then y-coordinate.
@@ -171,9 +360,10 @@ impl PublicKey {
     /// Verify signature on message assumed to be hashed, if needed.
     #[must_use = "The return value indicates if the message is authentic"]
     pub fn verify_prehashed(&self, prehashed_message: &[u8], signature: &Signature) -> bool {
-        let verifying_key: p256::ecdsa::VerifyingKey = self.0.into();
-        verifying_key
-            .verify_prehash(prehashed_message, &signature.0)
+        let z = bits2field(prehashed_message);
+        self.0
+            .as_affine()
+            .verify_prehashed(&z.into(), &signature.0)
             .is_ok()
     }
 
@@ -182,9 +372,101 @@ impl PublicKey {
     #[cfg_attr(docsrs, doc(cfg(feature = "prehash")))]
     #[must_use = "The return value indicates if the message is authentic"]
     pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
-        let verifier: p256::ecdsa::VerifyingKey = self.0.clone().into();
-        use p256::ecdsa::signature::Verifier;
-        verifier.verify(message, &signature.0).is_ok()
+        let prehashed_message = crate::sha256(message);
+        self.verify_prehashed(prehashed_message.as_ref(), signature)
+    }
+
+    /// Verify signatures on messages assumed to be hashed, if needed, yielding whether each
+    /// is authentic, in order.
+    ///
+    /// Items with the same public key share its tables of multiples. The last four
+    /// distinct keys stay prepared, so items need not be grouped by signer.
+    pub fn verify_prehashed_batch<'a>(
+        items: &'a [(&'a PublicKey, &'a [u8], &'a Signature)],
+    ) -> impl Iterator<Item = bool> + 'a {
+        let mut prepared: [Option<PreparedPublicKey>; BATCH_KEYS] = Default::default();
+        let mut next = 0;
+        items
+            .iter()
+            .map(move |(public_key, prehashed_message, signature)| {
+                let index = prepared
+                    .iter()
+                    .position(|p| matches!(p, Some(p) if p.public == **public_key))
+                    .unwrap_or_else(|| {
+                        // evict the key prepared longest ago
+                        let index = next;
+                        next = (next + 1) % BATCH_KEYS;
+                        prepared[index] = Some(PreparedPublicKey::new((*public_key).clone()));
+                        index
+                    });
+                prepared[index]
+                    .as_ref()
+                    .unwrap()
+                    .verify_prehashed(prehashed_message, signature)
+            })
+    }
+
+    /// Recover the public key from a signature on message assumed to be hashed, if needed.
+    ///
+    /// The hash must be at least 16 bytes long. Returns [`Error::InvalidSignature`] if
+    /// there is no public key for this recovery id, or the signature does not verify.
+    pub fn recover_from_prehash(
+        prehashed_message: &[u8],
+        signature: &Signature,
+        recovery_id: RecoveryId,
+    ) -> Result<Self> {
+        // NB: `p256` rejects shorter hashes
+        if prehashed_message.len() < 16 {
+            return Err(Error::InvalidLength);
+        }
+        let recovery_id =
+            ecdsa::RecoveryId::new(recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+        p256::ecdsa::VerifyingKey::recover_from_prehash(
+            prehashed_message,
+            &signature.0,
+            recovery_id,
+        )
+        .map(|verifying_key| PublicKey(verifying_key.into()))
+        .map_err(|_| Error::InvalidSignature)
+    }
+}
+
+impl PreparedPublicKey {
+    /// Prepare public key for verifying many signatures.
+    pub fn new(public_key: PublicKey) -> Self {
+        let table = verify::Table::new(&public_key);
+        PreparedPublicKey {
+            public: public_key,
+            table,
+        }
+    }
+
+    /// The public key this was prepared from.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Verify signature on message assumed to be hashed, if needed.
+    ///
+    /// The result is that of [`PublicKey::verify_prehashed`].
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn verify_prehashed(&self, prehashed_message: &[u8], signature: &Signature) -> bool {
+        self.table.verify_prehashed(prehashed_message, signature)
+    }
+
+    /// Verify signature on message, which is hashed with SHA-256 first.
+    #[cfg(feature = "prehash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "prehash")))]
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let prehashed_message = crate::sha256(message);
+        self.verify_prehashed(prehashed_message.as_ref(), signature)
+    }
+}
+
+impl From<PublicKey> for PreparedPublicKey {
+    fn from(public_key: PublicKey) -> Self {
+        Self::new(public_key)
     }
 }
 
@@ -204,12 +486,22 @@ impl Signature {
     /// Necessarily, bytes must be of length 64, and r and s must be integers
     /// in the range 1..=n-1, otherwise decoding fails.
     pub fn from_untagged_bytes(bytes: &[u8]) -> Result<Self> {
-        Ok(Signature(bytes.try_into()?))
+        if bytes.len() != 64 {
+            return Err(Error::InvalidLength);
+        }
+        Ok(Signature(
+            bytes.try_into().map_err(|_| Error::ScalarOutOfRange)?,
+        ))
     }
 
     /// Decode signature from SEC1 ASN.1 DER
+    ///
+    /// Decoding is strict: integers must be minimally encoded, there must be no trailing
+    /// data, and r and s must be integers in the range 1..=n-1.
+    ///
+    /// Unlike on the Cortex-M4 backend, this does not need the `sec1-signatures` feature.
     pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
-        Ok(Signature(p256::ecdsa::Signature::from_der(bytes)?))
+        Self::from_untagged_bytes(&crate::asn1::decode_signature(bytes)?)
     }
 
     /// Encode signature from big-endian r, then big-endian s, without framing.
@@ -223,16 +515,23 @@ impl Signature {
     #[cfg(feature = "sec1-signatures")]
     #[cfg_attr(docsrs, doc(cfg(feature = "sec1-signatures")))]
     pub fn to_sec1_bytes(&self, buffer: &mut [u8; 72]) -> usize {
-        let asn1_signature = self.0.to_der();
-        let n = asn1_signature.as_ref().len();
-        buffer[..n].copy_from_slice(asn1_signature.as_ref());
-        n
+        crate::asn1::encode_signature(&self.to_untagged_bytes(), buffer)
+    }
+}
+
+// NB: `p256::SecretKey` zeroizes itself on drop
+impl ZeroizeOnDrop for SecretKey {}
+
+impl Drop for Presignature {
+    fn drop(&mut self) {
+        self.r.zeroize();
+        self.k_inv.zeroize();
     }
 }
 
 impl SharedSecret {
     /// The secret (big-endian x-coordinate)
     pub fn as_bytes(&self) -> &[u8; 32] {
-        self.0.raw_secret_bytes().as_ref()
+        &self.0
     }
 }
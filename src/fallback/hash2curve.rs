@@ -0,0 +1,53 @@
+//! Hashing to the curve, following RFC 9380 with the suites `P256_XMD:SHA-256_SSWU_RO_`
+//! and `P256_XMD:SHA-256_SSWU_NU_`, and hashing to scalars, via `p256`.
+
+use elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+use p256::NistP256;
+use sha2::Sha256;
+
+use super::{ProjectivePoint, Scalar};
+use crate::{Error, Result};
+
+impl ProjectivePoint {
+    /// Hash the concatenated messages to the curve, with the suite `P256_XMD:SHA-256_SSWU_RO_` of RFC 9380.
+    ///
+    /// Returns [`Error::InvalidLength`] if the domain separation tag `dst` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash2curve")))]
+    pub fn hash_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<Self> {
+        // NB: `p256` only rejects an empty list of tags, not an empty tag
+        if dst.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        let point = NistP256::hash_from_bytes::<ExpandMsgXmd<Sha256>>(msgs, &[dst])?;
+        Ok(ProjectivePoint(point))
+    }
+
+    /// Encode the concatenated messages to the curve, with the suite `P256_XMD:SHA-256_SSWU_NU_` of RFC 9380.
+    ///
+    /// The output is not uniformly distributed, see [`ProjectivePoint::hash_to_curve`].
+    /// Returns [`Error::InvalidLength`] if the domain separation tag `dst` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash2curve")))]
+    pub fn encode_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<Self> {
+        if dst.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        let point = NistP256::encode_from_bytes::<ExpandMsgXmd<Sha256>>(msgs, &[dst])?;
+        Ok(ProjectivePoint(point))
+    }
+}
+
+impl Scalar {
+    /// Hash the concatenated messages to a scalar, with `hash_to_field` of RFC 9380 modulo n,
+    /// using `expand_message_xmd` with SHA-256.
+    ///
+    /// The 48 uniform bytes are reduced modulo n, so the output is statistically close to uniform.
+    /// Returns [`Error::InvalidLength`] if the domain separation tag `dst` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash2curve")))]
+    pub fn hash_to_scalar(msgs: &[&[u8]], dst: &[u8]) -> Result<Self> {
+        if dst.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        let scalar = NistP256::hash_to_scalar::<ExpandMsgXmd<Sha256>>(msgs, &[dst])?;
+        Ok(Scalar(scalar))
+    }
+}
@@ -0,0 +1,172 @@
+//! Points on the curve.
+
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use elliptic_curve::group::Group;
+
+use super::{PublicKey, Scalar};
+use crate::{Error, Result};
+
+/// Point on the curve other than the point at infinity, for protocols beyond ECDSA and ECDH.
+///
+/// This has the same representation and encodings as a [`PublicKey`], and converts to and from it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AffinePoint(pub(super) PublicKey);
+
+/// Point on the curve, possibly the point at infinity, for adding and doubling.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct ProjectivePoint(pub(super) p256::ProjectivePoint);
+
+/// Converts the result of a multiplication, which is the point at infinity only for a zero scalar.
+fn from_projective(point: p256::ProjectivePoint) -> Result<AffinePoint> {
+    p256::PublicKey::from_affine(point.to_affine())
+        .map(|public_key| AffinePoint(PublicKey(public_key)))
+        .map_err(|_| Error::ScalarOutOfRange)
+}
+
+impl AffinePoint {
+    /// The base point G.
+    pub fn generator() -> Self {
+        AffinePoint(PublicKey(
+            p256::PublicKey::from_affine(p256::AffinePoint::GENERATOR).unwrap(),
+        ))
+    }
+
+    /// Decode from the compressed or uncompressed SEC1 encoding, see [`PublicKey::from_sec1_bytes`].
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
+        PublicKey::from_sec1_bytes(bytes).map(AffinePoint)
+    }
+
+    /// Compressed encoding: `02 || Px` if Py is even and `03 || Px` if Py is odd
+    pub fn to_compressed_sec1_bytes(&self) -> [u8; 33] {
+        self.0.to_compressed_sec1_bytes()
+    }
+
+    /// Uncompressed encoding: `04 || Px || Py`.
+    pub fn to_uncompressed_sec1_bytes(&self) -> [u8; 65] {
+        self.0.to_uncompressed_sec1_bytes()
+    }
+
+    /// Big-endian representation of x-coordinate.
+    pub fn x(&self) -> [u8; 32] {
+        self.0.x()
+    }
+
+    /// Big-endian representation of y-coordinate.
+    pub fn y(&self) -> [u8; 32] {
+        self.0.y()
+    }
+
+    /// Multiply the base point by a scalar, in constant time.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if the scalar is zero, as the result
+    /// would be the point at infinity.
+    pub fn mul_base(scalar: &Scalar) -> Result<Self> {
+        from_projective(p256::ProjectivePoint::GENERATOR * scalar.0)
+    }
+
+    /// Multiply this point by a scalar, in constant time.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if the scalar is zero, as the result
+    /// would be the point at infinity.
+    pub fn mul(&self, scalar: &Scalar) -> Result<Self> {
+        from_projective(self.0 .0.to_projective() * scalar.0)
+    }
+}
+
+impl From<PublicKey> for AffinePoint {
+    fn from(public_key: PublicKey) -> Self {
+        AffinePoint(public_key)
+    }
+}
+
+impl From<AffinePoint> for PublicKey {
+    fn from(point: AffinePoint) -> Self {
+        point.0
+    }
+}
+
+impl ProjectivePoint {
+    /// The point at infinity.
+    pub fn identity() -> Self {
+        ProjectivePoint(p256::ProjectivePoint::IDENTITY)
+    }
+
+    /// The base point G.
+    pub fn generator() -> Self {
+        ProjectivePoint(p256::ProjectivePoint::GENERATOR)
+    }
+
+    /// Is this the point at infinity?
+    pub fn is_identity(&self) -> bool {
+        self.0.is_identity().into()
+    }
+
+    /// Convert to affine coordinates, unless this is the point at infinity.
+    pub fn to_affine(&self) -> Option<AffinePoint> {
+        p256::PublicKey::from_affine(self.0.to_affine())
+            .ok()
+            .map(|public_key| AffinePoint(PublicKey(public_key)))
+    }
+
+    /// Double this point.
+    pub fn double(&self) -> Self {
+        ProjectivePoint(self.0.double())
+    }
+}
+
+impl From<&AffinePoint> for ProjectivePoint {
+    fn from(point: &AffinePoint) -> Self {
+        ProjectivePoint(point.0 .0.to_projective())
+    }
+}
+
+impl From<AffinePoint> for ProjectivePoint {
+    fn from(point: AffinePoint) -> Self {
+        Self::from(&point)
+    }
+}
+
+impl Add for ProjectivePoint {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        ProjectivePoint(self.0 + other.0)
+    }
+}
+
+impl AddAssign for ProjectivePoint {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for ProjectivePoint {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        ProjectivePoint(self.0 - other.0)
+    }
+}
+
+impl SubAssign for ProjectivePoint {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for ProjectivePoint {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ProjectivePoint(-self.0)
+    }
+}
+
+impl core::fmt::Debug for ProjectivePoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ProjectivePoint")
+            .field(&self.to_affine())
+            .finish()
+    }
+}
@@ -0,0 +1,188 @@
+//! Integers modulo the order n of the base point.
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+use elliptic_curve::{
+    ff::PrimeField,
+    ops::{Invert, Reduce},
+    subtle::ConstantTimeEq,
+};
+use p256::U256;
+use rand_core::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::SecretKey;
+use crate::{Error, Result};
+
+/// Integer modulo the order n of the base point, for protocols beyond ECDSA and ECDH.
+///
+/// Zeroized on drop, as scalars are usually secret.
+#[derive(Clone)]
+pub struct Scalar(pub(super) p256::Scalar);
+
+/// Reduces a big-endian 256-bit integer modulo n.
+fn reduce(bytes: &[u8]) -> Scalar {
+    let bytes: [u8; 32] = bytes.try_into().unwrap();
+    Scalar(<p256::Scalar as Reduce<U256>>::reduce_bytes(&bytes.into()))
+}
+
+impl Scalar {
+    /// Zero.
+    pub const ZERO: Self = Scalar(p256::Scalar::ZERO);
+
+    /// One.
+    pub const ONE: Self = Scalar(p256::Scalar::ONE);
+
+    /// Generate a random `Scalar` in the range 1..=n-1.
+    ///
+    /// The implementation uses rejection sampling, exactly like [`SecretKey::random`].
+    pub fn random(rng: impl CryptoRng + RngCore) -> Self {
+        let mut rng = rng;
+        Scalar(*p256::NonZeroScalar::random(&mut rng))
+    }
+
+    /// Verifies that there are 32 bytes that correspond to a big-endian integer in the range 0..=n-1.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        let bytes: [u8; 32] = bytes
+            .as_ref()
+            .try_into()
+            .map_err(|_| Error::InvalidLength)?;
+        Option::from(p256::Scalar::from_repr(bytes.into()))
+            .map(Scalar)
+            .ok_or(Error::ScalarOutOfRange)
+    }
+
+    /// Big-endian representation as 32 bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes().into()
+    }
+
+    /// Reduces a big-endian 512-bit integer modulo n.
+    ///
+    /// For uniformly random input, the output is statistically close to uniform.
+    pub fn from_bytes_wide(bytes: &[u8; 64]) -> Self {
+        // NB: hi * 2^256 + lo = hi * R + lo (mod n), with R = 2^256 mod n
+        let r = <p256::Scalar as Reduce<U256>>::reduce(U256::from_be_hex(
+            "00000000ffffffff00000000000000004319055258e8617b0c46353d039cdaaf",
+        ));
+        let hi = reduce(&bytes[..32]);
+        let lo = reduce(&bytes[32..]);
+        Scalar(hi.0 * r + lo.0)
+    }
+
+    /// Is this zero?
+    pub fn is_zero(&self) -> bool {
+        self.0.ct_eq(&p256::Scalar::ZERO).into()
+    }
+
+    /// Multiplicative inverse, in constant time.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if this is zero.
+    pub fn invert(&self) -> Result<Self> {
+        Option::from(self.0.invert())
+            .map(Scalar)
+            .ok_or(Error::ScalarOutOfRange)
+    }
+
+    /// Multiplicative inverse, in variable time.
+    ///
+    /// Only use this for public scalars. Returns [`Error::ScalarOutOfRange`] if this is zero.
+    pub fn invert_vartime(&self) -> Result<Self> {
+        Option::from(Invert::invert_vartime(&self.0))
+            .map(Scalar)
+            .ok_or(Error::ScalarOutOfRange)
+    }
+}
+
+impl From<&SecretKey> for Scalar {
+    fn from(secret_key: &SecretKey) -> Self {
+        Scalar(*secret_key.0.to_nonzero_scalar())
+    }
+}
+
+impl Add<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: &Scalar) -> Scalar {
+        Scalar(self.0 + other.0)
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: Scalar) -> Scalar {
+        &self + &other
+    }
+}
+
+impl Sub<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: &Scalar) -> Scalar {
+        Scalar(self.0 - other.0)
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: Scalar) -> Scalar {
+        &self - &other
+    }
+}
+
+impl Mul<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: &Scalar) -> Scalar {
+        Scalar(self.0 * other.0)
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: Scalar) -> Scalar {
+        &self * &other
+    }
+}
+
+impl Neg for &Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        Scalar(-self.0)
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        -&self
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        // NB: in constant time, as scalars are usually secret
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl Eq for Scalar {}
+
+impl Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Scalar {}
+
+impl Drop for Scalar {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
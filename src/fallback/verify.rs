@@ -0,0 +1,88 @@
+//! ECDSA verification against a precomputed table for the public key.
+//!
+//! This mirrors `cortex_m4/verify.rs` on top of the `p256` arithmetic: a joint sliding
+//! window over both scalars, with tables of odd multiples of the base point and the
+//! public key.
+
+use elliptic_curve::{
+    group::Group,
+    ops::{Invert, Reduce},
+    point::AffineCoordinates,
+};
+use p256::{ProjectivePoint, Scalar, U256};
+
+use super::{PublicKey, Signature};
+use crate::{bits2field, window::slide};
+
+/// P, 3P, 5P, ..., 15P for the base point and a public key P.
+///
+/// NB: the tables stay projective, as `p256` has no batch inversion to normalize them
+/// cheaply, and mixed additions are hardly faster.
+#[derive(Clone)]
+pub(crate) struct Table {
+    base: [ProjectivePoint; 8],
+    key: [ProjectivePoint; 8],
+}
+
+/// Odd multiples P, 3P, ..., 15P.
+fn odd_multiples(point: ProjectivePoint) -> [ProjectivePoint; 8] {
+    let double = point.double();
+    let mut multiples = [point; 8];
+    for i in 1..8 {
+        multiples[i] = multiples[i - 1] + double;
+    }
+    multiples
+}
+
+/// Little-endian representation of a scalar.
+fn to_le_bytes(scalar: &Scalar) -> [u8; 32] {
+    let mut bytes: [u8; 32] = scalar.to_bytes().into();
+    bytes.reverse();
+    bytes
+}
+
+impl Table {
+    /// `PublicKey` is always on the curve, so there is nothing to check.
+    pub(crate) fn new(public_key: &PublicKey) -> Self {
+        // NB: `p256` has no precomputed table for the base point, so it is built per key
+        Table {
+            base: odd_multiples(ProjectivePoint::GENERATOR),
+            key: odd_multiples(public_key.0.to_projective()),
+        }
+    }
+
+    /// Verify signature on message assumed to be hashed, if needed.
+    pub(crate) fn verify_prehashed(&self, prehashed_message: &[u8], signature: &Signature) -> bool {
+        // NB: `Signature` is always in range, and everything is public
+        let (r, s) = signature.0.split_scalars();
+        let z = <Scalar as Reduce<U256>>::reduce_bytes(&bits2field(prehashed_message).into());
+        let w = *s.invert_vartime();
+        let slide_base = slide(&to_le_bytes(&(z * w)));
+        let slide_key = slide(&to_le_bytes(&(*r * w)));
+
+        let mut point = ProjectivePoint::IDENTITY;
+        for (base, key) in slide_base.iter().zip(slide_key.iter()).rev() {
+            point = point.double();
+            if *base != 0 {
+                let multiple = &self.base[(base.unsigned_abs() / 2) as usize];
+                point = if *base < 0 {
+                    point - multiple
+                } else {
+                    point + multiple
+                };
+            }
+            if *key != 0 {
+                let multiple = &self.key[(key.unsigned_abs() / 2) as usize];
+                point = if *key < 0 {
+                    point - multiple
+                } else {
+                    point + multiple
+                };
+            }
+        }
+        if bool::from(point.is_identity()) {
+            return false;
+        }
+        <Scalar as Reduce<U256>>::reduce_bytes(&point.to_affine().x()) == *r
+    }
+}
@@ -0,0 +1,151 @@
+//! JSON Web Key encoding of keys, shared by both backends.
+
+use alloc::string::String;
+
+use base64ct::{Base64UrlUnpadded, Encoding};
+use zeroize::Zeroizing;
+
+use crate::{Error, PublicKey, Result, SecretKey};
+
+/// Length of a base64url encoded coordinate or scalar, without padding.
+const ENCODED_LEN: usize = 43;
+
+/// Members of a JWK for P-256, as in [RFC 7518][rfc-7518], section 6.2.
+///
+/// Other members are ignored; serde_json rejects duplicate members.
+///
+/// [rfc-7518]: https://www.rfc-editor.org/rfc/rfc7518
+#[derive(serde::Deserialize)]
+struct Jwk {
+    kty: String,
+    crv: String,
+    x: String,
+    y: String,
+    d: Option<String>,
+}
+
+impl Jwk {
+    fn from_str(jwk: &str) -> Result<Self> {
+        let jwk: Self = serde_json::from_str(jwk).map_err(|_| Error::InvalidEncoding)?;
+        if jwk.kty != "EC" || jwk.crv != "P-256" {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+        Ok(jwk)
+    }
+
+    fn public_key(&self) -> Result<PublicKey> {
+        let mut untagged = [0u8; 64];
+        decode(&self.x, &mut untagged[..32])?;
+        decode(&self.y, &mut untagged[32..])?;
+        PublicKey::from_untagged_bytes(&untagged)
+    }
+}
+
+impl Drop for Jwk {
+    fn drop(&mut self) {
+        if let Some(d) = self.d.as_mut() {
+            zeroize::Zeroize::zeroize(d);
+        }
+    }
+}
+
+/// Decodes exactly 32 bytes, as RFC 7518 requires full-length coordinates and scalars.
+fn decode(encoded: &str, bytes: &mut [u8]) -> Result<()> {
+    if encoded.len() != ENCODED_LEN {
+        return Err(Error::InvalidLength);
+    }
+    Base64UrlUnpadded::decode(encoded, bytes).map_err(|_| Error::InvalidEncoding)?;
+    Ok(())
+}
+
+/// Writes `{"kty":"EC","crv":"P-256","x":"...","y":"..."`, leaving the object open.
+fn write_public_members(public_key: &PublicKey, jwk: &mut String) {
+    let mut buffer = [0u8; ENCODED_LEN];
+    jwk.push_str(r#"{"kty":"EC","crv":"P-256","x":""#);
+    jwk.push_str(Base64UrlUnpadded::encode(&public_key.x(), &mut buffer).unwrap());
+    jwk.push_str(r#"","y":""#);
+    jwk.push_str(Base64UrlUnpadded::encode(&public_key.y(), &mut buffer).unwrap());
+    jwk.push('"');
+}
+
+impl PublicKey {
+    /// Decode from a JSON Web Key with `kty` `EC` and `crv` `P-256`.
+    ///
+    /// The coordinates `x` and `y` must be full-length base64url without padding.
+    /// A private key parameter `d`, if present, is ignored.
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwk")))]
+    pub fn from_jwk_str(jwk: &str) -> Result<Self> {
+        Jwk::from_str(jwk)?.public_key()
+    }
+
+    /// Encode as JSON Web Key, with members `kty`, `crv`, `x`, and `y`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwk")))]
+    pub fn to_jwk_string(&self) -> String {
+        let mut jwk = String::with_capacity(128);
+        write_public_members(self, &mut jwk);
+        jwk.push('}');
+        jwk
+    }
+
+    /// SHA-256 JWK thumbprint of [RFC 7638][rfc-7638].
+    ///
+    /// [rfc-7638]: https://www.rfc-editor.org/rfc/rfc7638
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwk")))]
+    pub fn jwk_thumbprint(&self) -> [u8; 32] {
+        // required members only, in lexicographic order, without whitespace
+        const PREFIX: &[u8] = br#"{"crv":"P-256","kty":"EC","x":""#;
+        const INFIX: &[u8] = br#"","y":""#;
+        const SUFFIX: &[u8] = br#""}"#;
+        const LEN: usize = PREFIX.len() + INFIX.len() + SUFFIX.len() + 2 * ENCODED_LEN;
+
+        let mut canonical = [0u8; LEN];
+        let (prefix, rest) = canonical.split_at_mut(PREFIX.len());
+        prefix.copy_from_slice(PREFIX);
+        let (x, rest) = rest.split_at_mut(ENCODED_LEN);
+        Base64UrlUnpadded::encode(&self.x(), x).unwrap();
+        let (infix, rest) = rest.split_at_mut(INFIX.len());
+        infix.copy_from_slice(INFIX);
+        let (y, suffix) = rest.split_at_mut(ENCODED_LEN);
+        Base64UrlUnpadded::encode(&self.y(), y).unwrap();
+        suffix.copy_from_slice(SUFFIX);
+
+        crate::sha256(&canonical)
+    }
+}
+
+impl SecretKey {
+    /// Decode from a JSON Web Key with `kty` `EC` and `crv` `P-256`.
+    ///
+    /// The private key parameter `d` is required, and the public key `(x, y)`
+    /// must match it.
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwk")))]
+    pub fn from_jwk_str(jwk: &str) -> Result<Self> {
+        let jwk = Jwk::from_str(jwk)?;
+        let public_key = jwk.public_key()?;
+        let mut secret = Zeroizing::new([0u8; 32]);
+        decode(
+            jwk.d.as_deref().ok_or(Error::InvalidEncoding)?,
+            secret.as_mut(),
+        )?;
+        let secret_key = SecretKey::from_bytes(secret.as_ref())?;
+        if public_key.to_untagged_bytes() != secret_key.public_key().to_untagged_bytes() {
+            return Err(Error::KeyMismatch);
+        }
+        Ok(secret_key)
+    }
+
+    /// Encode as JSON Web Key, with members `kty`, `crv`, `x`, `y`, and `d`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwk")))]
+    pub fn to_jwk_string(&self) -> Zeroizing<String> {
+        // allocated once, so no copies of `d` are left behind
+        let mut jwk = Zeroizing::new(String::with_capacity(192));
+        write_public_members(&self.public_key(), &mut jwk);
+
+        let secret = Zeroizing::new(unsafe { self.to_bytes() });
+        let mut d = Zeroizing::new([0u8; ENCODED_LEN]);
+        jwk.push_str(r#","d":""#);
+        jwk.push_str(Base64UrlUnpadded::encode(secret.as_ref(), d.as_mut()).unwrap());
+        jwk.push_str(r#""}"#);
+        jwk
+    }
+}
@@ -0,0 +1,298 @@
+//! [JWS][rfc-7515] compact serialization with ES256.
+//!
+//! The signature is the 64 byte concatenation of `r` and `s`, as in
+//! [`Signature::to_untagged_bytes`]; DER signatures are rejected. Everything is written
+//! into caller-provided buffers, there is no allocation.
+//!
+//! The protected header is a JSON object, which must have member `"alg":"ES256"`,
+//! and must not have `crit`. Other members are not interpreted, and member names
+//! must not contain escapes.
+//!
+//! [rfc-7515]: https://www.rfc-editor.org/rfc/rfc7515
+
+use base64ct::{Base64UrlUnpadded, Encoding};
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::{Error, PublicKey, Result, SecretKey, Signature};
+
+/// Length of the base64url encoded signature.
+const SIGNATURE_LEN: usize = 86;
+
+/// Sign `payload` with the protected `header`, writing `header.payload.signature` into
+/// `buffer`.
+///
+/// Returns the length of the serialization, or [`Error::InvalidLength`] if the buffer
+/// is too small.
+pub fn sign(
+    header: &str,
+    payload: &[u8],
+    secret_key: &SecretKey,
+    rng: impl CryptoRng + RngCore,
+    buffer: &mut [u8],
+) -> Result<usize> {
+    check_header(header.as_bytes())?;
+
+    let mut len = encode(header.as_bytes(), buffer)?;
+    len += separator(&mut buffer[len..])?;
+    len += encode(payload, &mut buffer[len..])?;
+    let signature = secret_key.sign_prehashed(&Sha256::digest(&buffer[..len]), rng);
+
+    len += separator(&mut buffer[len..])?;
+    len += encode(&signature.to_untagged_bytes(), &mut buffer[len..])?;
+    Ok(len)
+}
+
+/// Verify a compact serialization, decoding its protected header and payload into `buffer`.
+///
+/// Returns the header and the payload if the signature is valid, and
+/// [`Error::InvalidSignature`] otherwise. Signatures that are not 64 bytes,
+/// e.g., DER, are [`Error::InvalidLength`].
+pub fn verify<'b>(
+    jws: &str,
+    public_key: &PublicKey,
+    buffer: &'b mut [u8],
+) -> Result<(&'b [u8], &'b [u8])> {
+    let mut parts = jws.split('.');
+    let (Some(header), Some(payload), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::InvalidEncoding);
+    };
+
+    if signature.len() != SIGNATURE_LEN {
+        return Err(Error::InvalidLength);
+    }
+    let mut untagged = [0u8; 64];
+    Base64UrlUnpadded::decode(signature, &mut untagged).map_err(|_| Error::InvalidEncoding)?;
+    let signature = Signature::from_untagged_bytes(&untagged)?;
+
+    let header_len = decode(header, buffer)?;
+    let (header, rest) = buffer.split_at_mut(header_len);
+    check_header(header)?;
+    let payload_len = decode(payload, rest)?;
+
+    let signing_input = &jws[..jws.len() - SIGNATURE_LEN - 1];
+    if !public_key.verify_prehashed(&Sha256::digest(signing_input), &signature) {
+        return Err(Error::InvalidSignature);
+    }
+    Ok((header, &rest[..payload_len]))
+}
+
+fn separator(buffer: &mut [u8]) -> Result<usize> {
+    *buffer.first_mut().ok_or(Error::InvalidLength)? = b'.';
+    Ok(1)
+}
+
+fn encode(bytes: &[u8], buffer: &mut [u8]) -> Result<usize> {
+    Base64UrlUnpadded::encode(bytes, buffer)
+        .map(str::len)
+        .map_err(|_| Error::InvalidLength)
+}
+
+fn decode(encoded: &str, buffer: &mut [u8]) -> Result<usize> {
+    Base64UrlUnpadded::decode(encoded, buffer)
+        .map(<[u8]>::len)
+        .map_err(|error| match error {
+            base64ct::Error::InvalidLength => Error::InvalidLength,
+            base64ct::Error::InvalidEncoding => Error::InvalidEncoding,
+        })
+}
+
+/// The header must be a JSON object with `"alg":"ES256"`, and without `crit`.
+fn check_header(header: &[u8]) -> Result<()> {
+    let mut json = Json { bytes: header };
+    let mut alg = None;
+
+    json.expect(b'{')?;
+    if !json.eat(b'}') {
+        loop {
+            let name = json.name()?;
+            json.expect(b':')?;
+            match name {
+                b"alg" if alg.is_none() => alg = Some(json.string()?),
+                b"alg" | b"crit" => return Err(Error::InvalidEncoding),
+                _ => json.value(MAX_DEPTH)?,
+            }
+            if json.eat(b'}') {
+                break;
+            }
+            json.expect(b',')?;
+        }
+    }
+    json.whitespace();
+    if !json.bytes.is_empty() {
+        return Err(Error::InvalidEncoding);
+    }
+
+    match alg {
+        Some(b"ES256") => Ok(()),
+        _ => Err(Error::UnsupportedAlgorithm),
+    }
+}
+
+/// Nesting depth up to which header values are checked.
+const MAX_DEPTH: usize = 16;
+
+/// Just enough JSON to check that a header is well-formed.
+struct Json<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Json<'a> {
+    fn whitespace(&mut self) {
+        while let [b' ' | b'\t' | b'\n' | b'\r', rest @ ..] = self.bytes {
+            self.bytes = rest;
+        }
+    }
+
+    /// Skips whitespace, and consumes `byte` if it is next.
+    fn eat(&mut self, byte: u8) -> bool {
+        self.whitespace();
+        match self.bytes {
+            [first, rest @ ..] if *first == byte => {
+                self.bytes = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        match self.eat(byte) {
+            true => Ok(()),
+            false => Err(Error::InvalidEncoding),
+        }
+    }
+
+    /// The raw contents of a string, with escapes validated but not resolved.
+    fn string(&mut self) -> Result<&'a [u8]> {
+        self.expect(b'"')?;
+        let start = self.bytes;
+        loop {
+            match self.bytes {
+                [b'"', rest @ ..] => {
+                    let contents = &start[..start.len() - self.bytes.len()];
+                    self.bytes = rest;
+                    return Ok(contents);
+                }
+                [b'\\', b'u', a, b, c, d, rest @ ..]
+                    if [a, b, c, d].iter().all(|x| x.is_ascii_hexdigit()) =>
+                {
+                    self.bytes = rest
+                }
+                [b'\\', b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't', rest @ ..] => {
+                    self.bytes = rest
+                }
+                [c, rest @ ..] if *c >= 0x20 && *c != b'\\' => self.bytes = rest,
+                _ => return Err(Error::InvalidEncoding),
+            }
+        }
+    }
+
+    /// A member name, which must not contain escapes.
+    fn name(&mut self) -> Result<&'a [u8]> {
+        let name = self.string()?;
+        match name.contains(&b'\\') {
+            true => Err(Error::InvalidEncoding),
+            false => Ok(name),
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<()> {
+        let depth = depth.checked_sub(1).ok_or(Error::InvalidEncoding)?;
+        self.whitespace();
+        match self.bytes.first() {
+            Some(b'"') => self.string().map(|_| ()),
+            Some(b'{') => {
+                self.expect(b'{')?;
+                if self.eat(b'}') {
+                    return Ok(());
+                }
+                loop {
+                    self.string()?;
+                    self.expect(b':')?;
+                    self.value(depth)?;
+                    if self.eat(b'}') {
+                        return Ok(());
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            Some(b'[') => {
+                self.expect(b'[')?;
+                if self.eat(b']') {
+                    return Ok(());
+                }
+                loop {
+                    self.value(depth)?;
+                    if self.eat(b']') {
+                        return Ok(());
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            _ => self.literal(),
+        }
+    }
+
+    /// `true`, `false`, `null`, or a number.
+    fn literal(&mut self) -> Result<()> {
+        for literal in [&b"true"[..], b"false", b"null"] {
+            if let Some(rest) = self.bytes.strip_prefix(literal) {
+                self.bytes = rest;
+                return Ok(());
+            }
+        }
+
+        let number = self
+            .bytes
+            .iter()
+            .position(|c| !matches!(c, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+            .unwrap_or(self.bytes.len());
+        let (number, rest) = self.bytes.split_at(number);
+        let number = number.strip_prefix(b"-").unwrap_or(number);
+        let digits = number
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(number.len());
+        let (integer, fraction_exponent) = number.split_at(digits);
+        let integer_valid = integer == b"0" || (!integer.is_empty() && integer[0] != b'0');
+        if !integer_valid || !valid_fraction_exponent(fraction_exponent) {
+            return Err(Error::InvalidEncoding);
+        }
+        self.bytes = rest;
+        Ok(())
+    }
+}
+
+/// Optional `.digits`, then optional `e`/`E`, optional sign, digits.
+fn valid_fraction_exponent(mut bytes: &[u8]) -> bool {
+    fn digits(bytes: &[u8]) -> (usize, &[u8]) {
+        let n = bytes
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(bytes.len());
+        (n, &bytes[n..])
+    }
+
+    if let Some(rest) = bytes.strip_prefix(b".") {
+        let (n, rest) = digits(rest);
+        if n == 0 {
+            return false;
+        }
+        bytes = rest;
+    }
+    if let [b'e' | b'E', rest @ ..] = bytes {
+        let rest = match rest {
+            [b'+' | b'-', rest @ ..] => rest,
+            rest => rest,
+        };
+        let (n, rest) = digits(rest);
+        if n == 0 {
+            return false;
+        }
+        bytes = rest;
+    }
+    bytes.is_empty()
+}
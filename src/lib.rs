@@ -12,14 +12,127 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![warn(missing_docs, rust_2018_idioms, unused_qualifications)]
 
+#[cfg(all(
+    any(
+        feature = "pem",
+        feature = "jwk",
+        all(feature = "ssh", feature = "alloc")
+    ),
+    any(cortex_m4, feature = "non-cortex-m4-fallback")
+))]
+extern crate alloc;
+
 /// Errors.
-#[derive(Copy, Clone, Debug)]
-pub struct Error;
+///
+/// Both backends return the same variant for the same invalid input.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Input has the wrong length.
+    InvalidLength,
+    /// Scalar (secret key, or signature component) is not in the range 1..=n-1.
+    ScalarOutOfRange,
+    /// Coordinates are not in the range 0..=p-1, or do not satisfy the curve equation.
+    PointNotOnCurve,
+    /// Encoding is not recognized, e.g., an unknown SEC1 tag.
+    InvalidEncoding,
+    /// ASN.1 DER is malformed, or not canonical.
+    MalformedDer,
+    /// Signing failed, and must be retried with a new presignature.
+    SigningFailed,
+    /// Algorithm or curve identifier is not `id-ecPublicKey` on P-256.
+    UnsupportedAlgorithm,
+    /// Embedded public key does not match the secret key.
+    KeyMismatch,
+    /// Signature verification failed.
+    InvalidSignature,
+    /// Opaque error from the `elliptic-curve` crate.
+    EllipticCurve,
+    /// Opaque error from the `ecdsa` (respectively, `signature`) crate.
+    Ecdsa,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Error::InvalidLength => "invalid length",
+            Error::ScalarOutOfRange => "scalar out of range",
+            Error::PointNotOnCurve => "point not on curve",
+            Error::InvalidEncoding => "invalid encoding",
+            Error::MalformedDer => "malformed DER",
+            Error::SigningFailed => "signing failed",
+            Error::UnsupportedAlgorithm => "unsupported algorithm",
+            Error::KeyMismatch => "key mismatch",
+            Error::InvalidSignature => "invalid signature",
+            Error::EllipticCurve => "elliptic curve error",
+            Error::Ecdsa => "ECDSA error",
+        })
+    }
+}
+
+// NB: this is also `std::error::Error`
+impl core::error::Error for Error {}
+
+#[cfg(feature = "der")]
+impl From<der::Error> for Error {
+    fn from(_: der::Error) -> Self {
+        Error::MalformedDer
+    }
+}
+
+#[cfg(feature = "elliptic-curve")]
+impl From<elliptic_curve::Error> for Error {
+    fn from(_: elliptic_curve::Error) -> Self {
+        Error::EllipticCurve
+    }
+}
+
+// NB: this is also `ecdsa::Error`
+impl From<signature::Error> for Error {
+    fn from(_: signature::Error) -> Self {
+        Error::Ecdsa
+    }
+}
 
 /// Result type.
 pub type Result<T> = core::result::Result<T, Error>;
 
-// pub mod traits;
+pub use signature;
+
+#[cfg(all(
+    any(feature = "sec1-signatures", feature = "spki", not(cortex_m4)),
+    any(cortex_m4, feature = "non-cortex-m4-fallback")
+))]
+mod asn1;
+#[cfg(all(feature = "cose", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+mod cbor;
+#[cfg(all(feature = "cose", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "cose")))]
+pub mod cose;
+#[cfg(all(feature = "jwk", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+mod jwk;
+#[cfg(all(feature = "jws", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "jws")))]
+pub mod jws;
+#[cfg(all(
+    feature = "rfc6979",
+    any(cortex_m4, feature = "non-cortex-m4-fallback")
+))]
+mod nonce;
+#[cfg(all(feature = "pkcs8", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+mod pkcs8;
+#[cfg(any(cortex_m4, feature = "non-cortex-m4-fallback"))]
+mod recovery;
+#[cfg(any(cortex_m4, feature = "non-cortex-m4-fallback"))]
+pub use recovery::RecoveryId;
+#[cfg(all(feature = "spki", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+mod spki;
+#[cfg(all(feature = "ssh", any(cortex_m4, feature = "non-cortex-m4-fallback")))]
+mod ssh;
+#[cfg(any(cortex_m4, feature = "non-cortex-m4-fallback"))]
+mod traits;
+#[cfg(any(cortex_m4, feature = "non-cortex-m4-fallback"))]
+mod window;
 
 /// Convenience function, calculates SHA256 hash digest of a slice of bytes.
 #[cfg(feature = "prehash")]
@@ -32,6 +145,18 @@ pub fn sha256(message: &[u8]) -> [u8; 32] {
     data.into()
 }
 
+/// Leftmost 256 bits of the hash, as big-endian integer.
+///
+/// This is what both `p256_sign` and `p256_verify` sign respectively verify,
+/// shorter hashes are padded with zeros on the left.
+#[cfg(any(cortex_m4, feature = "non-cortex-m4-fallback"))]
+pub(crate) fn bits2field(prehashed_message: &[u8]) -> [u8; 32] {
+    let mut z = [0u8; 32];
+    let l = core::cmp::min(prehashed_message.len(), 32);
+    z[32 - l..].copy_from_slice(&prehashed_message[..l]);
+    z
+}
+
 #[cfg(cortex_m4)]
 mod cortex_m4;
 #[cfg(cortex_m4)]
@@ -0,0 +1,55 @@
+//! Derivation of the ephemeral scalar `k` for ECDSA signatures.
+//!
+//! This is shared by both backends, so that they agree on the signatures
+//! they produce byte for byte.
+
+use rfc6979::HmacDrbg;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// Order of the base point, as big-endian bytes.
+const ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// Subtracts the order once if `z >= n`, in constant time.
+///
+/// As `z < 2^256 < 2n`, this fully reduces `z`.
+fn reduce_once(z: &mut [u8; 32]) {
+    let mut difference = [0u8; 32];
+    let mut borrow = 0i16;
+    for ((d, z), n) in difference.iter_mut().zip(z.iter()).zip(ORDER.iter()).rev() {
+        let t = *z as i16 - *n as i16 - borrow;
+        *d = t as u8;
+        borrow = (t >> 8) & 1;
+    }
+    // keep `z` if and only if the subtraction borrowed
+    let mask = (borrow as u8).wrapping_sub(1);
+    for (z, d) in z.iter_mut().zip(difference.iter()) {
+        *z ^= mask & (*z ^ d);
+    }
+}
+
+/// Candidates for `k`, as specified in [RFC 6979][rfc-6979], section 3.2.
+///
+/// Additional data, if any, is mixed into the seed as in section 3.6.
+///
+/// [rfc-6979]: https://datatracker.ietf.org/doc/html/rfc6979
+pub(crate) struct Nonces(HmacDrbg<Sha256>);
+
+impl Nonces {
+    pub(crate) fn new(secret: &[u8; 32], prehashed_message: &[u8], additional_data: &[u8]) -> Self {
+        // `bits2octets(h1)`
+        let mut z = crate::bits2field(prehashed_message);
+        reduce_once(&mut z);
+        Self(HmacDrbg::new(secret, &z, additional_data))
+    }
+
+    /// Next big-endian candidate, which may be out of the range 1..=n-1.
+    pub(crate) fn next(&mut self) -> Zeroizing<[u8; 32]> {
+        let mut k = Zeroizing::new([0u8; 32]);
+        self.0.fill_bytes(k.as_mut());
+        k
+    }
+}
@@ -0,0 +1,193 @@
+//! PKCS#8 and SEC1 encodings of secret keys, shared by both backends.
+
+use der::{
+    asn1::{BitStringRef, ObjectIdentifier, OctetStringRef},
+    Decode, Encode,
+};
+use zeroize::Zeroizing;
+
+use crate::{
+    asn1::{AlgorithmIdentifier, SECP256R1},
+    Error, PublicKey, Result, SecretKey,
+};
+
+/// Length of [`SecretKey::to_sec1_der`].
+const SEC1_DER_LEN: usize = 121;
+/// Length of the `ECPrivateKey` inside [`SecretKey::to_pkcs8_der`].
+const INNER_SEC1_DER_LEN: usize = 109;
+/// Length of [`SecretKey::to_pkcs8_der`].
+const PKCS8_DER_LEN: usize = 138;
+
+/// `ECPrivateKey` in [RFC 5915][rfc-5915], section 3.
+///
+/// [rfc-5915]: https://www.rfc-editor.org/rfc/rfc5915
+#[derive(der::Sequence)]
+struct EcPrivateKey<'a> {
+    version: u8,
+    private_key: OctetStringRef<'a>,
+    #[asn1(context_specific = "0", optional = "true")]
+    parameters: Option<ObjectIdentifier>,
+    #[asn1(context_specific = "1", optional = "true")]
+    public_key: Option<BitStringRef<'a>>,
+}
+
+/// `OneAsymmetricKey` in [RFC 5958][rfc-5958], section 2, without attributes.
+///
+/// With version 0, this is the `PrivateKeyInfo` of PKCS#8.
+///
+/// [rfc-5958]: https://www.rfc-editor.org/rfc/rfc5958
+#[derive(der::Sequence)]
+struct PrivateKeyInfo<'a> {
+    version: u8,
+    algorithm: AlgorithmIdentifier<'a>,
+    private_key: OctetStringRef<'a>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    public_key: Option<BitStringRef<'a>>,
+}
+
+impl EcPrivateKey<'_> {
+    fn to_secret_key(&self) -> Result<SecretKey> {
+        if self.version != 1 {
+            return Err(Error::MalformedDer);
+        }
+        if self.parameters.is_some_and(|curve| curve != SECP256R1) {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+        let secret_key = SecretKey::from_bytes(self.private_key.as_bytes())?;
+        check_public_key(&secret_key, self.public_key)?;
+        Ok(secret_key)
+    }
+}
+
+/// An embedded public key must be a valid SEC1 encoding of the public key of `secret_key`.
+fn check_public_key(secret_key: &SecretKey, public_key: Option<BitStringRef<'_>>) -> Result<()> {
+    let Some(public_key) = public_key else {
+        return Ok(());
+    };
+    let public_key = PublicKey::from_sec1_bytes(public_key.as_bytes().ok_or(Error::MalformedDer)?)?;
+    if public_key.to_untagged_bytes() != secret_key.public_key().to_untagged_bytes() {
+        return Err(Error::KeyMismatch);
+    }
+    Ok(())
+}
+
+impl SecretKey {
+    /// Decode from the `ECPrivateKey` of [SEC 1][sec-1], section C.4, as DER.
+    ///
+    /// Curve parameters, if present, must name P-256, and an embedded public key,
+    /// if present, must match the secret key.
+    ///
+    /// [sec-1]: http://www.secg.org/sec1-v2.pdf
+    #[cfg_attr(docsrs, doc(cfg(feature = "pkcs8")))]
+    pub fn from_sec1_der(bytes: &[u8]) -> Result<Self> {
+        EcPrivateKey::from_der(bytes)?.to_secret_key()
+    }
+
+    /// Encode as `ECPrivateKey` of [SEC 1][sec-1], section C.4, in DER.
+    ///
+    /// As OpenSSL does, includes the curve parameters and the uncompressed public key.
+    ///
+    /// [sec-1]: http://www.secg.org/sec1-v2.pdf
+    #[cfg_attr(docsrs, doc(cfg(feature = "pkcs8")))]
+    pub fn to_sec1_der(&self) -> Zeroizing<[u8; SEC1_DER_LEN]> {
+        let mut der = Zeroizing::new([0u8; SEC1_DER_LEN]);
+        self.encode_sec1_der(Some(SECP256R1), der.as_mut_slice());
+        der
+    }
+
+    /// Decode from the `PrivateKeyInfo` of [PKCS#8][pkcs-8], as DER.
+    ///
+    /// The algorithm must be `id-ecPublicKey` on the named curve P-256. Embedded public keys,
+    /// either in the `ECPrivateKey` or in a version 2 `OneAsymmetricKey`, must match the
+    /// secret key. Attributes are not supported.
+    ///
+    /// [pkcs-8]: https://www.rfc-editor.org/rfc/rfc5958
+    #[cfg_attr(docsrs, doc(cfg(feature = "pkcs8")))]
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self> {
+        let info = PrivateKeyInfo::from_der(bytes)?;
+        match (info.version, info.public_key) {
+            (0, None) | (1, _) => {}
+            _ => return Err(Error::MalformedDer),
+        }
+        info.algorithm.check()?;
+        let secret_key = EcPrivateKey::from_der(info.private_key.as_bytes())?.to_secret_key()?;
+        check_public_key(&secret_key, info.public_key)?;
+        Ok(secret_key)
+    }
+
+    /// Encode as version 1 `PrivateKeyInfo` of [PKCS#8][pkcs-8], in DER.
+    ///
+    /// As OpenSSL does, the `ECPrivateKey` includes the uncompressed public key.
+    ///
+    /// [pkcs-8]: https://www.rfc-editor.org/rfc/rfc5958
+    #[cfg_attr(docsrs, doc(cfg(feature = "pkcs8")))]
+    pub fn to_pkcs8_der(&self) -> Zeroizing<[u8; PKCS8_DER_LEN]> {
+        let mut inner = Zeroizing::new([0u8; INNER_SEC1_DER_LEN]);
+        self.encode_sec1_der(None, inner.as_mut_slice());
+
+        let info = PrivateKeyInfo {
+            version: 0,
+            algorithm: AlgorithmIdentifier::p256(),
+            private_key: OctetStringRef::new(inner.as_slice()).unwrap(),
+            public_key: None,
+        };
+        let mut der = Zeroizing::new([0u8; PKCS8_DER_LEN]);
+        let len = info.encode_to_slice(der.as_mut_slice()).unwrap().len();
+        debug_assert_eq!(len, PKCS8_DER_LEN);
+        der
+    }
+
+    fn encode_sec1_der(&self, parameters: Option<ObjectIdentifier>, buffer: &mut [u8]) {
+        let secret = Zeroizing::new(unsafe { self.to_bytes() });
+        let public = self.public_key().to_uncompressed_sec1_bytes();
+        let key = EcPrivateKey {
+            version: 1,
+            private_key: OctetStringRef::new(secret.as_slice()).unwrap(),
+            parameters,
+            public_key: Some(BitStringRef::from_bytes(&public).unwrap()),
+        };
+        let len = key.encode_to_slice(buffer).unwrap().len();
+        debug_assert_eq!(len, buffer.len());
+    }
+}
+
+#[cfg(feature = "pem")]
+mod pem {
+    use alloc::string::String;
+
+    use zeroize::Zeroizing;
+
+    use crate::{
+        asn1::{decode_pem, encode_pem},
+        Result, SecretKey,
+    };
+
+    const SEC1_LABEL: &str = "EC PRIVATE KEY";
+    const PKCS8_LABEL: &str = "PRIVATE KEY";
+
+    impl SecretKey {
+        /// Decode from PEM with label `EC PRIVATE KEY`, see [`Self::from_sec1_der`].
+        #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+        pub fn from_sec1_pem(pem: &str) -> Result<Self> {
+            Self::from_sec1_der(&decode_pem(pem, SEC1_LABEL)?)
+        }
+
+        /// Encode as PEM with label `EC PRIVATE KEY`, see [`Self::to_sec1_der`].
+        #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+        pub fn to_sec1_pem(&self) -> Zeroizing<String> {
+            Zeroizing::new(encode_pem(self.to_sec1_der().as_slice(), SEC1_LABEL))
+        }
+
+        /// Decode from PEM with label `PRIVATE KEY`, see [`Self::from_pkcs8_der`].
+        #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+        pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+            Self::from_pkcs8_der(&decode_pem(pem, PKCS8_LABEL)?)
+        }
+
+        /// Encode as PEM with label `PRIVATE KEY`, see [`Self::to_pkcs8_der`].
+        #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+        pub fn to_pkcs8_pem(&self) -> Zeroizing<String> {
+            Zeroizing::new(encode_pem(self.to_pkcs8_der().as_slice(), PKCS8_LABEL))
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Recovery of the public key from an ECDSA signature.
+
+/// Recovery id, which selects one of the (up to four) public keys a signature
+/// on a given message could have been made with.
+///
+/// Bit 0 is whether the y-coordinate of `R = kG` is odd, bit 1 is whether its
+/// x-coordinate was reduced modulo n to obtain `r`. The encoding agrees with
+/// `ecdsa::RecoveryId`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct RecoveryId(u8);
+
+impl RecoveryId {
+    /// Largest valid recovery id.
+    pub const MAX: u8 = 3;
+
+    /// Recovery id from its two bits.
+    pub const fn new(is_y_odd: bool, is_x_reduced: bool) -> Self {
+        Self((is_x_reduced as u8) << 1 | is_y_odd as u8)
+    }
+
+    /// Decode from a byte in the range 0..=3.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        if byte <= Self::MAX {
+            Some(Self(byte))
+        } else {
+            None
+        }
+    }
+
+    /// Encode as byte in the range 0..=3.
+    pub const fn to_byte(self) -> u8 {
+        self.0
+    }
+
+    /// Is the y-coordinate of `R` odd?
+    pub const fn is_y_odd(self) -> bool {
+        self.0 & 1 != 0
+    }
+
+    /// Is the x-coordinate of `R` at least n?
+    pub const fn is_x_reduced(self) -> bool {
+        self.0 & 2 != 0
+    }
+}
@@ -0,0 +1,60 @@
+//! `SubjectPublicKeyInfo` encoding of public keys, shared by both backends.
+
+use der::{asn1::BitStringRef, Decode, Encode};
+
+use crate::{asn1::AlgorithmIdentifier, Error, PublicKey, Result};
+
+/// `SubjectPublicKeyInfo` in [RFC 5280][rfc-5280], section 4.1.2.7.
+///
+/// [rfc-5280]: https://www.rfc-editor.org/rfc/rfc5280
+#[derive(der::Sequence)]
+struct SubjectPublicKeyInfo<'a> {
+    algorithm: AlgorithmIdentifier<'a>,
+    subject_public_key: BitStringRef<'a>,
+}
+
+impl PublicKey {
+    /// Decode from X.509 `SubjectPublicKeyInfo`, as DER.
+    ///
+    /// The algorithm must be `id-ecPublicKey` on the named curve P-256,
+    /// the point may be compressed or uncompressed, see [`Self::from_sec1_bytes`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "spki")))]
+    pub fn from_public_key_der(bytes: &[u8]) -> Result<Self> {
+        let info = SubjectPublicKeyInfo::from_der(bytes)?;
+        info.algorithm.check()?;
+        Self::from_sec1_bytes(
+            info.subject_public_key
+                .as_bytes()
+                .ok_or(Error::MalformedDer)?,
+        )
+    }
+
+    /// Encode as X.509 `SubjectPublicKeyInfo` with uncompressed point, in DER.
+    ///
+    /// Returns the length of the encoding, which is always 91.
+    #[cfg_attr(docsrs, doc(cfg(feature = "spki")))]
+    pub fn to_public_key_der(&self, buffer: &mut [u8; 91]) -> usize {
+        let point = self.to_uncompressed_sec1_bytes();
+        let info = SubjectPublicKeyInfo {
+            algorithm: AlgorithmIdentifier::p256(),
+            subject_public_key: BitStringRef::from_bytes(&point).unwrap(),
+        };
+        info.encode_to_slice(buffer.as_mut()).unwrap().len()
+    }
+
+    /// Decode from PEM with label `PUBLIC KEY`, see [`Self::from_public_key_der`].
+    #[cfg(feature = "pem")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+    pub fn from_public_key_pem(pem: &str) -> Result<Self> {
+        Self::from_public_key_der(&crate::asn1::decode_pem(pem, "PUBLIC KEY")?)
+    }
+
+    /// Encode as PEM with label `PUBLIC KEY`, see [`Self::to_public_key_der`].
+    #[cfg(feature = "pem")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+    pub fn to_public_key_pem(&self) -> alloc::string::String {
+        let mut der = [0u8; 91];
+        let len = self.to_public_key_der(&mut der);
+        crate::asn1::encode_pem(&der[..len], "PUBLIC KEY")
+    }
+}
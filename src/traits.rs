@@ -0,0 +1,152 @@
+//! Implementations of the [`signature`] traits.
+//!
+//! These are expressed in terms of the inherent methods, so that both
+//! backends implement exactly the same traits.
+//!
+//! The deterministic `PrehashSigner` and `Signer` need the `rfc6979` feature,
+//! the traits on messages hashed with SHA-256 need the `prehash` feature.
+
+use rand_core::CryptoRngCore;
+#[cfg(feature = "rfc6979")]
+use signature::hazmat::PrehashSigner;
+use signature::hazmat::{PrehashVerifier, RandomizedPrehashSigner};
+use signature::SignatureEncoding;
+#[cfg(all(feature = "prehash", feature = "rfc6979"))]
+use signature::Signer;
+#[cfg(feature = "prehash")]
+use signature::{RandomizedSigner, Verifier};
+
+use crate::{Keypair, PublicKey, SecretKey, Signature};
+
+impl From<crate::Error> for signature::Error {
+    #[cfg(feature = "std")]
+    fn from(error: crate::Error) -> Self {
+        signature::Error::from_source(error)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn from(_: crate::Error) -> Self {
+        signature::Error::new()
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> signature::Result<Self> {
+        Ok(Signature::from_untagged_bytes(bytes)?)
+    }
+}
+
+impl From<Signature> for [u8; 64] {
+    fn from(signature: Signature) -> Self {
+        signature.to_untagged_bytes()
+    }
+}
+
+impl SignatureEncoding for Signature {
+    type Repr = [u8; 64];
+}
+
+impl signature::Keypair for Keypair {
+    type VerifyingKey = PublicKey;
+
+    fn verifying_key(&self) -> PublicKey {
+        self.public.clone()
+    }
+}
+
+impl signature::Keypair for SecretKey {
+    type VerifyingKey = PublicKey;
+
+    fn verifying_key(&self) -> PublicKey {
+        self.public_key()
+    }
+}
+
+#[cfg(feature = "rfc6979")]
+impl PrehashSigner<Signature> for SecretKey {
+    fn sign_prehash(&self, prehash: &[u8]) -> signature::Result<Signature> {
+        Ok(self.sign_prehashed_deterministic(prehash))
+    }
+}
+
+impl RandomizedPrehashSigner<Signature> for SecretKey {
+    fn sign_prehash_with_rng(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        prehash: &[u8],
+    ) -> signature::Result<Signature> {
+        Ok(self.sign_prehashed(prehash, rng))
+    }
+}
+
+#[cfg(all(feature = "prehash", feature = "rfc6979"))]
+impl Signer<Signature> for SecretKey {
+    fn try_sign(&self, msg: &[u8]) -> signature::Result<Signature> {
+        self.sign_prehash(&crate::sha256(msg))
+    }
+}
+
+#[cfg(feature = "prehash")]
+impl RandomizedSigner<Signature> for SecretKey {
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        msg: &[u8],
+    ) -> signature::Result<Signature> {
+        self.sign_prehash_with_rng(rng, &crate::sha256(msg))
+    }
+}
+
+#[cfg(feature = "rfc6979")]
+impl PrehashSigner<Signature> for Keypair {
+    fn sign_prehash(&self, prehash: &[u8]) -> signature::Result<Signature> {
+        self.secret.sign_prehash(prehash)
+    }
+}
+
+impl RandomizedPrehashSigner<Signature> for Keypair {
+    fn sign_prehash_with_rng(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        prehash: &[u8],
+    ) -> signature::Result<Signature> {
+        self.secret.sign_prehash_with_rng(rng, prehash)
+    }
+}
+
+#[cfg(all(feature = "prehash", feature = "rfc6979"))]
+impl Signer<Signature> for Keypair {
+    fn try_sign(&self, msg: &[u8]) -> signature::Result<Signature> {
+        self.secret.try_sign(msg)
+    }
+}
+
+#[cfg(feature = "prehash")]
+impl RandomizedSigner<Signature> for Keypair {
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        msg: &[u8],
+    ) -> signature::Result<Signature> {
+        self.secret.try_sign_with_rng(rng, msg)
+    }
+}
+
+impl PrehashVerifier<Signature> for PublicKey {
+    fn verify_prehash(&self, prehash: &[u8], signature: &Signature) -> signature::Result<()> {
+        if self.verify_prehashed(prehash, signature) {
+            Ok(())
+        } else {
+            Err(signature::Error::new())
+        }
+    }
+}
+
+#[cfg(feature = "prehash")]
+impl Verifier<Signature> for PublicKey {
+    fn verify(&self, msg: &[u8], signature: &Signature) -> signature::Result<()> {
+        self.verify_prehash(&crate::sha256(msg), signature)
+    }
+}
@@ -0,0 +1,47 @@
+//! Sliding windows for variable time double scalar multiplication in ECDSA verification.
+//!
+//! Both backends verify against tables of the odd multiples P, 3P, ..., 15P of the base
+//! point and the public key, indexed by the digits computed here.
+
+/// Signed sliding window representation of a little-endian integer, with digits that
+/// are zero or odd in the range -15..=15.
+///
+/// This is `slide_257` from `p256-cortex-m4.c`.
+pub(crate) fn slide(scalar: &[u8; 32]) -> [i8; 257] {
+    let mut r = [0i8; 257];
+    for (i, digit) in r.iter_mut().take(256).enumerate() {
+        *digit = (scalar[i / 8] >> (i % 8) & 1) as i8;
+    }
+
+    for i in 0..256 {
+        if r[i] == 0 {
+            continue;
+        }
+        let mut b = 1;
+        while b <= 4 && i + b < 256 {
+            if r[i + b] != 0 {
+                let shifted = r[i + b] << b;
+                if r[i] + shifted <= 15 {
+                    r[i] += shifted;
+                    r[i + b] = 0;
+                } else if r[i] - shifted >= -15 {
+                    r[i] -= shifted;
+                    // carry into the next zero digit
+                    loop {
+                        r[i + b] = 0;
+                        b += 1;
+                        if r[i + b] == 0 {
+                            r[i + b] = 1;
+                            b -= 1;
+                            break;
+                        }
+                    }
+                } else {
+                    break;
+                }
+            }
+            b += 1;
+        }
+    }
+    r
+}
@@ -2,9 +2,14 @@ use std::env;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(cortex_m4)");
     println!("cargo:rerun-if-changed=P256-Cortex-M4/p256-cortex-m4.h");
     println!("cargo:rerun-if-changed=P256-Cortex-M4/p256-cortex-m4.c");
+    println!("cargo:rerun-if-changed=P256-Cortex-M4/p256-cortex-m4-asm-gcc.S");
+    println!("cargo:rerun-if-changed=p256-cortex-m4-internal.c");
+    println!("cargo:rerun-if-changed=p256-cortex-m4-field.S");
     println!("cargo:rerun-if-changed=p256-cortex-m4-range-checks.h");
+    println!("cargo:rerun-if-changed=p256-cortex-m4-internal.h");
 
     let target = env::var("TARGET")?;
 
@@ -17,8 +22,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let builder = builder
             .flag("-std=c11")
-            .file("P256-Cortex-M4/p256-cortex-m4.c")
-            .file("P256-Cortex-M4/p256-cortex-m4-asm-gcc.S")
+            .file("p256-cortex-m4-internal.c")
+            .file("p256-cortex-m4-field.S")
             .flag("-march=armv7e-m");
 
         builder.compile("p256-cortex-m4-sys");
@@ -30,6 +35,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let bindings = bindgen::Builder::default()
                 .header("P256-Cortex-M4/p256-cortex-m4.h")
                 .header("p256-cortex-m4-range-checks.h")
+                .header("p256-cortex-m4-internal.h")
                 .clang_arg(format!("--target={}", target))
                 .use_core()
                 .ctypes_prefix("cty")
@@ -0,0 +1,9 @@
+// Compiles the C part of P256-Cortex-M4, and exports those of its static definitions
+// that are declared in p256-cortex-m4-internal.h.
+//
+// The subrepo is included rather than edited, so it can be updated with git-subrepo.
+
+#include "P256-Cortex-M4/p256-cortex-m4.c"
+#include "p256-cortex-m4-internal.h"
+
+const uint32_t (*const P256_basepoint_precomp)[8][2][8] = &p256_basepoint_precomp;
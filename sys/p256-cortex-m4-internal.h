@@ -0,0 +1,126 @@
+#include <stdbool.h>
+#include <stdint.h>
+/**
+ * Converts the argument, as little-endian integer in the range `0..=p-1`, to Montgomery form.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_to_montgomery(uint32_t aR[8], const uint32_t a[8]);
+/**
+ * Converts the argument, as little-endian integer in Montgomery form, back from Montgomery form.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_from_montgomery(uint32_t a[8], const uint32_t aR[8]);
+/**
+ * Multiplies two little-endian integers modulo the order `n` of the base point.
+ *
+ * The inputs may be any 256-bit integers, the output is reduced.
+ */
+void P256_mul_mod_n(uint32_t res[8], const uint32_t a[8], const uint32_t b[8]);
+/**
+ * Inverts a little-endian integer in the range `1..=n-1` modulo `n`, in constant time.
+ */
+void P256_mod_n_inv(uint32_t out[8], const uint32_t in[8]);
+/**
+ * Reduces a little-endian 256-bit integer modulo `n`.
+ */
+void P256_reduce_mod_n_32bytes(uint32_t res[8], const uint32_t a[8]);
+/**
+ * Computes the y-coordinate with the given parity, for the x-coordinate in the range `0..=p-1`.
+ *
+ * Returns false if there is no point on the curve with this x-coordinate.
+ * Neither input nor output are in Montgomery form.
+ */
+bool P256_decompress_point(uint32_t y[8], const uint32_t x[8], uint32_t y_parity);
+/**
+ * Adds (or subtracts) the second point to (from) the first point, in Jacobian coordinates in Montgomery form.
+ *
+ * If `p2_is_affine` is set, the second point only consists of x and y, and its z is taken to be one.
+ * The second point must not be the point at infinity; the first point is the point at infinity if its z is zero.
+ * All other inputs are handled, including doubling.
+ */
+void P256_add_sub_j(uint32_t jacobian_point1[3][8], const uint32_t (*point2)[8], bool is_sub, bool p2_is_affine);
+/**
+ * Converts a point in Jacobian coordinates to affine coordinates, all in Montgomery form.
+ */
+void P256_jacobian_to_affine(uint32_t affine_mont_x[8], uint32_t affine_mont_y[8], const uint32_t jacobian_mont[3][8]);
+/**
+ * Doubles a point in Jacobian coordinates in Montgomery form.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_double_j(uint32_t jacobian_point_out[3][8], const uint32_t jacobian_point_in[3][8]);
+/**
+ * Checks that `r`, as little-endian integer, is the x-coordinate of the point modulo `n`.
+ *
+ * The point is in Jacobian coordinates in Montgomery form, this is the last step of ECDSA verification.
+ */
+bool P256_verify_last_step(const uint32_t r[8], const uint32_t jacobian_point[3][8]);
+/**
+ * Checks that the point, in affine coordinates in Montgomery form, satisfies the curve equation.
+ */
+bool P256_point_is_on_curve(const uint32_t x_mont[8], const uint32_t y_mont[8]);
+/**
+ * Negates a little-endian integer in the range `1..=p-1` modulo `p`, if `should_negate` is one, in constant time.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_negate_mod_p_if(uint32_t out[8], const uint32_t in[8], uint32_t should_negate);
+/**
+ * Adds two little-endian integers modulo the order `n` of the base point.
+ *
+ * The inputs may be any 256-bit integers, the output is reduced. The output and input pointers may refer to the same location.
+ */
+void P256_add_mod_n(uint32_t res[8], const uint32_t a[8], const uint32_t b[8]);
+/**
+ * Negates a little-endian integer in the range `1..=n-1` modulo `n`, if `should_negate` is one, in constant time.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_negate_mod_n_if(uint32_t out[8], const uint32_t in[8], uint32_t should_negate);
+/**
+ * Multiplies two little-endian integers in the range `0..=p-1` in Montgomery form, modulo `p`.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_mul_mod_p(uint32_t res[8], const uint32_t a[8], const uint32_t b[8]);
+/**
+ * Squares a little-endian integer in the range `0..=p-1` in Montgomery form, modulo `p`.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_sqr_mod_p(uint32_t res[8], const uint32_t a[8]);
+/**
+ * Adds two little-endian integers in the range `0..=p-1` modulo `p`.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_add_mod_p(uint32_t res[8], const uint32_t a[8], const uint32_t b[8]);
+/**
+ * Subtracts two little-endian integers in the range `0..=p-1` modulo `p`.
+ *
+ * The output and input pointers may refer to the same location.
+ */
+void P256_sub_mod_p(uint32_t res[8], const uint32_t a[8], const uint32_t b[8]);
+/**
+ * Inverts a little-endian integer in the range `0..=p-1` in Montgomery form modulo `p`, in constant time.
+ *
+ * Zero is mapped to zero. The output and input pointers may refer to the same location.
+ */
+void P256_mod_p_inv(uint32_t res[8], const uint32_t a[8]);
+/**
+ * Raises a little-endian integer in the range `0..=p-1` in Montgomery form to the power `(p+1)/4` modulo `p`, in constant time.
+ *
+ * This is a square root if one exists, which the caller must check by squaring.
+ * The output and input pointers may refer to the same location.
+ */
+void P256_mod_p_sqrt(uint32_t res[8], const uint32_t a[8]);
+/**
+ * Reduces a little-endian 512-bit integer modulo `n`, with Barrett reduction.
+ */
+void P256_reduce_wide_mod_n(uint32_t res[8], const uint32_t a[16]);
+/**
+ * G, 3G, 5G, ..., 15G for the base point G, in affine coordinates in Montgomery form.
+ */
+extern const uint32_t (*const P256_basepoint_precomp)[8][2][8];
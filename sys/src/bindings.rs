@@ -502,3 +502,139 @@ extern "C" {
     #[doc = " In other words, that it is in the range `0..=p-1`, where `p = 2^256 - 2^224 + 2^192 + 2^96 - 1`."]
     pub fn P256_check_range_p(a: *const u32) -> bool;
 }
+extern "C" {
+    #[doc = " Converts the argument, as little-endian integer in the range `0..=p-1`, to Montgomery form."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_to_montgomery(aR: *mut u32, a: *const u32);
+}
+extern "C" {
+    #[doc = " Converts the argument, as little-endian integer in Montgomery form, back from Montgomery form."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_from_montgomery(a: *mut u32, aR: *const u32);
+}
+extern "C" {
+    #[doc = " Multiplies two little-endian integers modulo the order `n` of the base point."]
+    #[doc = ""]
+    #[doc = " The inputs may be any 256-bit integers, the output is reduced."]
+    pub fn P256_mul_mod_n(res: *mut u32, a: *const u32, b: *const u32);
+}
+extern "C" {
+    #[doc = " Inverts a little-endian integer in the range `1..=n-1` modulo `n`, in constant time."]
+    pub fn P256_mod_n_inv(out: *mut u32, in_: *const u32);
+}
+extern "C" {
+    #[doc = " Reduces a little-endian 256-bit integer modulo `n`."]
+    pub fn P256_reduce_mod_n_32bytes(res: *mut u32, a: *const u32);
+}
+extern "C" {
+    #[doc = " Computes the y-coordinate with the given parity, for the x-coordinate in the range `0..=p-1`."]
+    #[doc = ""]
+    #[doc = " Returns false if there is no point on the curve with this x-coordinate."]
+    #[doc = " Neither input nor output are in Montgomery form."]
+    pub fn P256_decompress_point(y: *mut u32, x: *const u32, y_parity: u32) -> bool;
+}
+extern "C" {
+    #[doc = " Adds (or subtracts) the second point to (from) the first point, in Jacobian coordinates in Montgomery form."]
+    #[doc = ""]
+    #[doc = " If `p2_is_affine` is set, the second point only consists of x and y, and its z is taken to be one."]
+    #[doc = " The second point must not be the point at infinity; the first point is the point at infinity if its z is zero."]
+    #[doc = " All other inputs are handled, including doubling."]
+    pub fn P256_add_sub_j(
+        jacobian_point1: *mut [u32; 8usize],
+        point2: *const [u32; 8usize],
+        is_sub: bool,
+        p2_is_affine: bool,
+    );
+}
+extern "C" {
+    #[doc = " Converts a point in Jacobian coordinates to affine coordinates, all in Montgomery form."]
+    pub fn P256_jacobian_to_affine(
+        affine_mont_x: *mut u32,
+        affine_mont_y: *mut u32,
+        jacobian_mont: *const [u32; 8usize],
+    );
+}
+extern "C" {
+    #[doc = " Doubles a point in Jacobian coordinates in Montgomery form."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_double_j(
+        jacobian_point_out: *mut [u32; 8usize],
+        jacobian_point_in: *const [u32; 8usize],
+    );
+}
+extern "C" {
+    #[doc = " Checks that `r`, as little-endian integer, is the x-coordinate of the point modulo `n`."]
+    #[doc = ""]
+    #[doc = " The point is in Jacobian coordinates in Montgomery form, this is the last step of ECDSA verification."]
+    pub fn P256_verify_last_step(r: *const u32, jacobian_point: *const [u32; 8usize]) -> bool;
+}
+extern "C" {
+    #[doc = " Checks that the point, in affine coordinates in Montgomery form, satisfies the curve equation."]
+    pub fn P256_point_is_on_curve(x_mont: *const u32, y_mont: *const u32) -> bool;
+}
+extern "C" {
+    #[doc = " Negates a little-endian integer in the range `1..=p-1` modulo `p`, if `should_negate` is one, in constant time."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_negate_mod_p_if(out: *mut u32, in_: *const u32, should_negate: u32);
+}
+extern "C" {
+    #[doc = " Adds two little-endian integers modulo the order `n` of the base point."]
+    #[doc = ""]
+    #[doc = " The inputs may be any 256-bit integers, the output is reduced. The output and input pointers may refer to the same location."]
+    pub fn P256_add_mod_n(res: *mut u32, a: *const u32, b: *const u32);
+}
+extern "C" {
+    #[doc = " Negates a little-endian integer in the range `1..=n-1` modulo `n`, if `should_negate` is one, in constant time."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_negate_mod_n_if(out: *mut u32, in_: *const u32, should_negate: u32);
+}
+extern "C" {
+    #[doc = " Multiplies two little-endian integers in the range `0..=p-1` in Montgomery form, modulo `p`."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_mul_mod_p(res: *mut u32, a: *const u32, b: *const u32);
+}
+extern "C" {
+    #[doc = " Squares a little-endian integer in the range `0..=p-1` in Montgomery form, modulo `p`."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_sqr_mod_p(res: *mut u32, a: *const u32);
+}
+extern "C" {
+    #[doc = " Adds two little-endian integers in the range `0..=p-1` modulo `p`."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_add_mod_p(res: *mut u32, a: *const u32, b: *const u32);
+}
+extern "C" {
+    #[doc = " Subtracts two little-endian integers in the range `0..=p-1` modulo `p`."]
+    #[doc = ""]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_sub_mod_p(res: *mut u32, a: *const u32, b: *const u32);
+}
+extern "C" {
+    #[doc = " Inverts a little-endian integer in the range `0..=p-1` in Montgomery form modulo `p`, in constant time."]
+    #[doc = ""]
+    #[doc = " Zero is mapped to zero. The output and input pointers may refer to the same location."]
+    pub fn P256_mod_p_inv(res: *mut u32, a: *const u32);
+}
+extern "C" {
+    #[doc = " Raises a little-endian integer in the range `0..=p-1` in Montgomery form to the power `(p+1)/4` modulo `p`, in constant time."]
+    #[doc = ""]
+    #[doc = " This is a square root if one exists, which the caller must check by squaring."]
+    #[doc = " The output and input pointers may refer to the same location."]
+    pub fn P256_mod_p_sqrt(res: *mut u32, a: *const u32);
+}
+extern "C" {
+    #[doc = " Reduces a little-endian 512-bit integer modulo `n`, with Barrett reduction."]
+    pub fn P256_reduce_wide_mod_n(res: *mut u32, a: *const u32);
+}
+extern "C" {
+    #[doc = " G, 3G, 5G, ..., 15G for the base point G, in affine coordinates in Montgomery form."]
+    pub static P256_basepoint_precomp: *const [[[u32; 8usize]; 2usize]; 8usize];
+}
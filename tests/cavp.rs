@@ -0,0 +1,26 @@
+//! Runs the NIST CAVP vectors on the host.
+//!
+//! The vendored vectors are run by the `#[test]`s the suite declares. Besides these,
+//! any response files of `186-4ecdsatestvectors.zip` (`KeyPair.rsp`, `PKV.rsp`,
+//! `SigGen.txt`, `SigVer.rsp`) and `KASTestVectorsECC.zip` (`KAS_ECC_CDH_PrimitiveTest.txt`)
+//! copied into `tests/cavp/` are run as well.
+
+#[path = "cavp/suite.rs"]
+mod suite;
+
+#[test]
+fn response_files() {
+    let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cavp");
+    for entry in std::fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(kind) = suite::Kind::from_file_name(name) {
+            let count = suite::run(kind, &std::fs::read_to_string(&path).unwrap());
+            assert!(count > 0, "no P-256 records in {}", name);
+            eprintln!("{}: {} P-256 records", name, count);
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Data-driven tests on NIST [CAVP][cavp] response files.
+//!
+//! Parses the `.rsp` format of the FIPS 186-4 ECDSA (`KeyPair`, `PKV`, `SigGen`, `SigVer`)
+//! and the SP 800-56A KAS ECC CDH primitive test vectors, and runs their P-256 records.
+//! Messages are hashed with the SHA variant of their section, and digests longer than
+//! 256 bits exercise the truncation of `hash_to_z`.
+//!
+//! Like the conformance suite, this is `no_std`, so that it can be run on the host and
+//! on the target. [`TESTS`] run the vendored vectors, the host harness runs them as the
+//! `#[test]`s declared alongside.
+//!
+//! [cavp]: https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program
+
+#![allow(dead_code)]
+
+use p256_cortex_m4::{PublicKey, SecretKey, Signature};
+use sha2::Digest;
+
+/// Declares [`TESTS`], and a `#[test]` for each of them when included by the host harness.
+macro_rules! tests {
+    ($($name:ident,)*) => {
+        /// All tests of the suite, by name.
+        pub const TESTS: &[(&str, fn())] = &[$((stringify!($name), $name),)*];
+
+        #[cfg(test)]
+        mod host {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name();
+                }
+            )*
+        }
+    };
+}
+
+tests! {
+    keypair,
+    pkv,
+    siggen,
+    sigver,
+    kas,
+}
+
+const KEYPAIR: &str = include_str!("vendored/KeyPair.txt");
+const PKV: &str = include_str!("vendored/PKV.txt");
+const SIGGEN: &str = include_str!("vendored/SigGen.txt");
+const SIGVER: &str = include_str!("vendored/SigVer.txt");
+const KAS: &str = include_str!("vendored/KAS_ECC_CDH_PrimitiveTest.txt");
+
+pub fn keypair() {
+    assert_eq!(run(Kind::KeyPair, KEYPAIR), 10);
+}
+
+pub fn pkv() {
+    assert_eq!(run(Kind::Pkv, PKV), 10);
+}
+
+pub fn siggen() {
+    assert_eq!(run(Kind::SigGen, SIGGEN), 15);
+}
+
+pub fn sigver() {
+    assert_eq!(run(Kind::SigVer, SIGVER), 85);
+}
+
+pub fn kas() {
+    assert_eq!(run(Kind::Kas, KAS), 25);
+}
+
+/// Kinds of response files.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    KeyPair,
+    Pkv,
+    SigGen,
+    SigVer,
+    Kas,
+}
+
+impl Kind {
+    /// Recognizes the response files of `186-4ecdsatestvectors.zip` and `KASTestVectorsECC.zip`.
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        [
+            ("KeyPair", Kind::KeyPair),
+            ("PKV", Kind::Pkv),
+            ("SigGen", Kind::SigGen),
+            ("SigVer", Kind::SigVer),
+            ("KAS_ECC_CDH_PrimitiveTest", Kind::Kas),
+        ]
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, kind)| *kind)
+    }
+}
+
+/// Runs all P-256 records of a response file, returns how many there were.
+///
+/// Records on other curves, or with hash functions other than SHA-1 and SHA-2,
+/// are skipped.
+pub fn run(kind: Kind, rsp: &str) -> usize {
+    let check = match kind {
+        Kind::KeyPair => check_key_pair,
+        Kind::Pkv => check_pkv,
+        Kind::SigGen => check_sig_gen,
+        Kind::SigVer => check_sig_ver,
+        Kind::Kas => check_kas,
+    };
+    Records::new(rsp)
+        .filter(|record| record.curve() == "P-256")
+        .filter(|record| check(record))
+        .count()
+}
+
+/// A block of `key = value` lines, in the section of the last `[...]` line.
+struct Record<'a> {
+    section: &'a str,
+    fields: [(&'a str, &'a str); 16],
+    len: usize,
+}
+
+impl<'a> Record<'a> {
+    fn get(&self, key: &str) -> Option<&'a str> {
+        self.fields[..self.len]
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| *v)
+    }
+
+    /// Either from a `Curve` field, or from sections like `[P-256]` and `[P-256,SHA-224]`.
+    fn curve(&self) -> &'a str {
+        self.get("Curve")
+            .unwrap_or_else(|| self.section.split(',').next().unwrap())
+    }
+
+    fn hash(&self) -> Option<&'a str> {
+        self.section.split(',').nth(1)
+    }
+
+    /// A 256-bit big-endian integer, `None` if it does not fit.
+    fn integer(&self, key: &str) -> Option<[u8; 32]> {
+        let mut buffer = [0u8; 66];
+        let bytes = unhex(self.get(key)?, &mut buffer)?;
+        let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+        let bytes = &bytes[start..];
+        if bytes.len() > 32 {
+            return None;
+        }
+        let mut integer = [0u8; 32];
+        integer[32 - bytes.len()..].copy_from_slice(bytes);
+        Some(integer)
+    }
+
+    /// Concatenation of two 256-bit integers, as for untagged points and signatures.
+    fn pair(&self, x: &str, y: &str) -> Option<[u8; 64]> {
+        let mut pair = [0u8; 64];
+        pair[..32].copy_from_slice(&self.integer(x)?);
+        pair[32..].copy_from_slice(&self.integer(y)?);
+        Some(pair)
+    }
+
+    /// Either the `Digest`, or the hash of the `Msg` with the function of the section.
+    fn digest<'b>(&self, buffer: &'b mut [u8; 64]) -> Option<&'b [u8]> {
+        if let Some(digest) = self.get("Digest") {
+            return unhex(digest, buffer).map(|digest| &*digest);
+        }
+        let mut message = [0u8; 128];
+        let message = unhex(self.get("Msg")?, &mut message)?;
+        let len = match self.hash()? {
+            "SHA-1" => copy(buffer, &sha1::Sha1::digest(message)),
+            "SHA-224" => copy(buffer, &sha2::Sha224::digest(message)),
+            "SHA-256" => copy(buffer, &sha2::Sha256::digest(message)),
+            "SHA-384" => copy(buffer, &sha2::Sha384::digest(message)),
+            "SHA-512" => copy(buffer, &sha2::Sha512::digest(message)),
+            _ => return None,
+        };
+        Some(&buffer[..len])
+    }
+}
+
+fn copy(buffer: &mut [u8], digest: &[u8]) -> usize {
+    buffer[..digest.len()].copy_from_slice(digest);
+    digest.len()
+}
+
+fn unhex<'b>(hex: &str, buffer: &'b mut [u8]) -> Option<&'b mut [u8]> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    let hex = hex.as_bytes();
+    if !hex.len().is_multiple_of(2) || hex.len() / 2 > buffer.len() {
+        return None;
+    }
+    let bytes = &mut buffer[..hex.len() / 2];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+        *byte = nibble(pair[0])? << 4 | nibble(pair[1])?;
+    }
+    Some(bytes)
+}
+
+struct Records<'a> {
+    lines: core::str::Lines<'a>,
+    section: &'a str,
+}
+
+impl<'a> Records<'a> {
+    fn new(rsp: &'a str) -> Self {
+        Self {
+            lines: rsp.lines(),
+            section: "",
+        }
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Record<'a>> {
+        let mut record = Record {
+            section: self.section,
+            fields: [("", ""); 16],
+            len: 0,
+        };
+        for line in self.lines.by_ref() {
+            let line = line.trim();
+            if line.is_empty() {
+                if record.len > 0 {
+                    return Some(record);
+                }
+            } else if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                self.section = section;
+                record.section = section;
+            } else if let Some((key, value)) = line.split_once('=') {
+                if !line.starts_with('#') {
+                    assert!(record.len < record.fields.len(), "record too long");
+                    record.fields[record.len] = (key.trim(), value.trim());
+                    record.len += 1;
+                }
+            }
+        }
+        if record.len > 0 {
+            Some(record)
+        } else {
+            None
+        }
+    }
+}
+
+/// The secret key `d` must have the public key `(Qx, Qy)`.
+fn check_key_pair(record: &Record<'_>) -> bool {
+    let (Some(d), Some(q)) = (record.integer("d"), record.pair("Qx", "Qy")) else {
+        return false;
+    };
+    let secret_key = SecretKey::from_bytes(d).unwrap();
+    assert_eq!(secret_key.public_key().to_untagged_bytes(), q);
+    true
+}
+
+/// The public key `(Qx, Qy)` must be accepted if and only if the `Result` is `P`.
+fn check_pkv(record: &Record<'_>) -> bool {
+    let Some(result) = record.get("Result") else {
+        return false;
+    };
+    let valid = record
+        .pair("Qx", "Qy")
+        .map(|q| PublicKey::from_untagged_bytes(&q).is_ok())
+        .unwrap_or(false);
+    assert_eq!(valid, result.starts_with('P'), "{:?}", record.get("Qx"));
+    true
+}
+
+/// The key pair must match, and the signature `(R, S)` must verify.
+///
+/// As the API does not allow choosing `k`, signatures themselves cannot be reproduced.
+fn check_sig_gen(record: &Record<'_>) -> bool {
+    let mut buffer = [0u8; 64];
+    let Some(digest) = record.digest(&mut buffer) else {
+        return false;
+    };
+    assert!(check_key_pair(record));
+    let public_key = PublicKey::from_untagged_bytes(&record.pair("Qx", "Qy").unwrap()).unwrap();
+    let signature = Signature::from_untagged_bytes(&record.pair("R", "S").unwrap()).unwrap();
+    assert!(public_key.verify_prehashed(digest, &signature));
+    true
+}
+
+/// The signature `(R, S)` must verify if and only if the `Result` is `P`.
+///
+/// Also understands the `Invalid = Y` of BoringSSL's files, and their `X` and `Y`.
+fn check_sig_ver(record: &Record<'_>) -> bool {
+    let mut buffer = [0u8; 64];
+    let Some(digest) = record.digest(&mut buffer) else {
+        return false;
+    };
+    let expected = match record.get("Result") {
+        Some(result) => result.starts_with('P'),
+        None => record.get("Invalid") != Some("Y"),
+    };
+    let public_key = record
+        .pair("Qx", "Qy")
+        .or_else(|| record.pair("X", "Y"))
+        .and_then(|q| PublicKey::from_untagged_bytes(&q).ok());
+    let signature = record
+        .pair("R", "S")
+        .and_then(|rs| Signature::from_untagged_bytes(&rs).ok());
+    let verified = match (public_key, signature) {
+        (Some(public_key), Some(signature)) => public_key.verify_prehashed(digest, &signature),
+        _ => false,
+    };
+    assert_eq!(verified, expected, "{:?}", record.get("R"));
+    true
+}
+
+/// The secret key `dIUT` must have public key `(QIUTx, QIUTy)`, and agree on `ZIUT`
+/// with the public key `(QCAVSx, QCAVSy)`.
+fn check_kas(record: &Record<'_>) -> bool {
+    let (Some(d), Some(q), Some(peer), Some(z)) = (
+        record.integer("dIUT"),
+        record.pair("QIUTx", "QIUTy"),
+        record.pair("QCAVSx", "QCAVSy"),
+        record.integer("ZIUT"),
+    ) else {
+        return false;
+    };
+    let secret_key = SecretKey::from_bytes(d).unwrap();
+    assert_eq!(secret_key.public_key().to_untagged_bytes(), q);
+    let peer = PublicKey::from_untagged_bytes(&peer).unwrap();
+    assert_eq!(secret_key.agree(&peer).as_bytes(), &z);
+    true
+}
@@ -0,0 +1,6 @@
+//! Runs the backend conformance suite on the host.
+//!
+//! The `#[test]`s are declared by the suite itself, from the same list as its `TESTS`.
+
+#[path = "conformance/suite.rs"]
+mod suite;
@@ -0,0 +1,20 @@
+//! Runs the Wycheproof vectors on the host.
+
+#[path = "wycheproof/suite.rs"]
+mod suite;
+
+#[cfg(feature = "sec1-signatures")]
+#[test]
+fn ecdsa_der() {
+    suite::ecdsa_der();
+}
+
+#[test]
+fn ecdsa_p1363() {
+    suite::ecdsa_p1363();
+}
+
+#[test]
+fn ecdh() {
+    suite::ecdh();
+}
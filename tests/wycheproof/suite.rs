@@ -0,0 +1,218 @@
+//! Data-driven tests on the vendored [Wycheproof][wycheproof] vectors.
+//!
+//! Like the conformance suite, this is `no_std` (but needs `alloc`), so that it
+//! can be run on the host and on the target; harnesses run [`TESTS`].
+//!
+//! [wycheproof]: https://github.com/C2SP/wycheproof
+
+#![allow(dead_code)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use p256_cortex_m4::{PublicKey, SecretKey, Signature};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// All tests of the suite, by name.
+pub const TESTS: &[(&str, fn())] = &[
+    #[cfg(feature = "sec1-signatures")]
+    ("ecdsa_der", ecdsa_der),
+    ("ecdsa_p1363", ecdsa_p1363),
+    ("ecdh", ecdh),
+];
+
+const ECDSA_DER: &[u8] = include_bytes!("ecdsa_secp256r1_sha256_test.json");
+const ECDSA_P1363: &[u8] = include_bytes!("ecdsa_secp256r1_sha256_p1363_test.json");
+const ECDH: &[u8] = include_bytes!("ecdh_secp256r1_test.json");
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Outcome {
+    Valid,
+    Acceptable,
+    Invalid,
+}
+
+#[derive(Deserialize)]
+struct EcdsaFile<'a> {
+    #[serde(rename = "numberOfTests")]
+    number_of_tests: usize,
+    #[serde(rename = "testGroups", borrow)]
+    groups: Vec<EcdsaGroup<'a>>,
+}
+
+#[derive(Deserialize)]
+struct EcdsaGroup<'a> {
+    #[serde(rename = "publicKey", borrow)]
+    public_key: EcdsaPublicKey<'a>,
+    sha: &'a str,
+    #[serde(borrow)]
+    tests: Vec<EcdsaTest<'a>>,
+}
+
+#[derive(Deserialize)]
+struct EcdsaPublicKey<'a> {
+    uncompressed: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EcdsaTest<'a> {
+    #[serde(rename = "tcId")]
+    id: u32,
+    msg: &'a str,
+    sig: &'a str,
+    result: Outcome,
+}
+
+#[derive(Deserialize)]
+struct EcdhFile<'a> {
+    #[serde(rename = "numberOfTests")]
+    number_of_tests: usize,
+    #[serde(rename = "testGroups", borrow)]
+    groups: Vec<EcdhGroup<'a>>,
+}
+
+#[derive(Deserialize)]
+struct EcdhGroup<'a> {
+    curve: &'a str,
+    #[serde(borrow)]
+    tests: Vec<EcdhTest<'a>>,
+}
+
+#[derive(Deserialize)]
+struct EcdhTest<'a> {
+    #[serde(rename = "tcId")]
+    id: u32,
+    public: &'a str,
+    private: &'a str,
+    shared: &'a str,
+    result: Outcome,
+}
+
+fn unhex(hex: &str) -> Vec<u8> {
+    fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => panic!("invalid hex"),
+        }
+    }
+    assert!(hex.len().is_multiple_of(2), "invalid hex");
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| nibble(pair[0]) << 4 | nibble(pair[1]))
+        .collect()
+}
+
+/// Runs `verify` on all tests of an ECDSA file, checking it against the expected outcome.
+fn ecdsa(json: &[u8], parse: fn(&[u8]) -> p256_cortex_m4::Result<Signature>) {
+    let file: EcdsaFile<'_> = serde_json::from_slice(json).unwrap();
+
+    let mut count = 0;
+    for group in &file.groups {
+        assert_eq!(group.sha, "SHA-256");
+        let public_key = PublicKey::from_sec1_bytes(&unhex(group.public_key.uncompressed)).unwrap();
+
+        for test in &group.tests {
+            let prehashed_message = Sha256::digest(unhex(test.msg));
+            let verified = match parse(&unhex(test.sig)) {
+                Ok(signature) => public_key.verify_prehashed(&prehashed_message, &signature),
+                Err(_) => false,
+            };
+            match test.result {
+                Outcome::Valid => assert!(verified, "tcId {} should verify", test.id),
+                Outcome::Invalid => assert!(!verified, "tcId {} should not verify", test.id),
+                Outcome::Acceptable => {}
+            }
+            count += 1;
+        }
+    }
+    assert_eq!(count, file.number_of_tests);
+}
+
+#[cfg(feature = "sec1-signatures")]
+pub fn ecdsa_der() {
+    ecdsa(ECDSA_DER, Signature::from_sec1_bytes);
+}
+
+pub fn ecdsa_p1363() {
+    ecdsa(ECDSA_P1363, Signature::from_untagged_bytes);
+}
+
+/// Decodes a DER `SubjectPublicKeyInfo` on P-256.
+#[cfg(feature = "spki")]
+fn public_key(spki: &[u8]) -> Option<PublicKey> {
+    PublicKey::from_public_key_der(spki).ok()
+}
+
+/// Decodes a DER `SubjectPublicKeyInfo` on P-256, by stripping the known prefixes.
+///
+/// Other encodings, e.g. with explicit curve parameters, are not supported.
+#[cfg(not(feature = "spki"))]
+fn public_key(spki: &[u8]) -> Option<PublicKey> {
+    spki_to_sec1(spki).and_then(|sec1| PublicKey::from_sec1_bytes(sec1).ok())
+}
+
+/// Extracts the SEC1 encoding from a DER `SubjectPublicKeyInfo` on P-256.
+#[cfg(not(feature = "spki"))]
+fn spki_to_sec1(spki: &[u8]) -> Option<&[u8]> {
+    const UNCOMPRESSED: &[u8] = &[
+        0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+    ];
+    const COMPRESSED: &[u8] = &[
+        0x30, 0x39, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x22, 0x00,
+    ];
+    match spki.len() {
+        91 => spki.strip_prefix(UNCOMPRESSED),
+        59 => spki.strip_prefix(COMPRESSED),
+        _ => None,
+    }
+}
+
+/// Big-endian private key, with leading zeros removed or added to make it 32 bytes.
+fn private_key(bytes: &[u8]) -> [u8; 32] {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    let mut private_key = [0u8; 32];
+    private_key[32 - bytes.len()..].copy_from_slice(bytes);
+    private_key
+}
+
+pub fn ecdh() {
+    let file: EcdhFile<'_> = serde_json::from_slice(ECDH).unwrap();
+
+    let mut count = 0;
+    for group in &file.groups {
+        assert_eq!(group.curve, "secp256r1");
+
+        for test in &group.tests {
+            let secret_key = SecretKey::from_bytes(private_key(&unhex(test.private))).unwrap();
+            let public = unhex(test.public);
+            let shared =
+                public_key(&public).map(|public_key| *secret_key.agree(&public_key).as_bytes());
+
+            let expected = unhex(test.shared);
+            match test.result {
+                Outcome::Valid => assert_eq!(
+                    shared.as_ref().map(|shared| &shared[..]),
+                    Some(&expected[..]),
+                    "tcId {}",
+                    test.id
+                ),
+                Outcome::Acceptable => {
+                    if let Some(shared) = shared {
+                        assert_eq!(shared[..], expected[..], "tcId {}", test.id);
+                    }
+                }
+                Outcome::Invalid => assert!(shared.is_none(), "tcId {} should fail", test.id),
+            }
+            count += 1;
+        }
+    }
+    assert_eq!(count, file.number_of_tests);
+}